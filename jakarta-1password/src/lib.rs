@@ -0,0 +1,398 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use jakarta_http::{HttpClient, HttpError, ReqwestClient};
+use jakarta_json::{Value, ValueExt};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum OpError {
+    #[error("invalid secret reference, expected op://<vault>/<item>[/<section>]/<field>")]
+    InvalidReference,
+    #[error("op cli failed: {0}")]
+    Cli(String),
+    #[error("connect request failed: {0}")]
+    Connect(#[from] HttpError),
+    #[error("connect returned status {0}")]
+    Status(u16),
+    #[error("{0} not found")]
+    NotFound(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct SecretReference<'a> {
+    vault: &'a str,
+    item: &'a str,
+    section: Option<&'a str>,
+    field: &'a str,
+}
+
+impl<'a> SecretReference<'a> {
+    fn parse(reference: &'a str) -> Result<Self, OpError> {
+        let path = reference
+            .trim()
+            .strip_prefix("op://")
+            .ok_or(OpError::InvalidReference)?;
+        let segments = path.split('/').collect::<Vec<_>>();
+
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return Err(OpError::InvalidReference);
+        }
+
+        match segments.as_slice() {
+            [vault, item, field] => Ok(Self {
+                vault,
+                item,
+                section: None,
+                field,
+            }),
+            [vault, item, section, field] => Ok(Self {
+                vault,
+                item,
+                section: Some(section),
+                field,
+            }),
+            _ => Err(OpError::InvalidReference),
+        }
+    }
+}
+
+enum Backend {
+    Cli,
+    Connect {
+        client: Box<dyn HttpClient + Send + Sync>,
+        host: String,
+        token: String,
+        timeout: Duration,
+    },
+}
+
+pub struct OpCommand {
+    backend: Backend,
+}
+
+impl OpCommand {
+    pub fn cli() -> Self {
+        Self {
+            backend: Backend::Cli,
+        }
+    }
+
+    pub fn connect(host: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            backend: Backend::Connect {
+                client: Box::new(ReqwestClient::new()),
+                host: host.into().trim_end_matches('/').to_owned(),
+                token: token.into(),
+                timeout: Duration::from_secs(10),
+            },
+        }
+    }
+
+    pub fn with_client(mut self, new_client: impl HttpClient + Send + Sync + 'static) -> Self {
+        if let Backend::Connect { client, .. } = &mut self.backend {
+            *client = Box::new(new_client);
+        }
+        self
+    }
+
+    pub fn with_timeout(mut self, new_timeout: Duration) -> Self {
+        if let Backend::Connect { timeout, .. } = &mut self.backend {
+            *timeout = new_timeout;
+        }
+        self
+    }
+
+    async fn read(&self, reference: &str) -> Result<String, OpError> {
+        let parsed = SecretReference::parse(reference)?;
+
+        match &self.backend {
+            Backend::Cli => read_cli(reference),
+            Backend::Connect {
+                client,
+                host,
+                token,
+                timeout,
+            } => {
+                let connect = Connect {
+                    client: client.as_ref(),
+                    host,
+                    headers: vec![("Authorization".to_owned(), format!("Bearer {token}"))],
+                    timeout: *timeout,
+                };
+
+                connect.read(&parsed).await
+            }
+        }
+    }
+}
+
+fn read_cli(reference: &str) -> Result<String, OpError> {
+    let output = std::process::Command::new("op")
+        .arg("read")
+        .arg("--no-newline")
+        .arg(reference.trim())
+        .output()
+        .map_err(|err| OpError::Cli(err.to_string()))?;
+
+    if !output.status.success() {
+        return Err(OpError::Cli(
+            String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        ));
+    }
+
+    String::from_utf8(output.stdout).map_err(|err| OpError::Cli(err.to_string()))
+}
+
+struct Connect<'a> {
+    client: &'a (dyn HttpClient + Send + Sync),
+    host: &'a str,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+}
+
+impl Connect<'_> {
+    async fn read(&self, reference: &SecretReference<'_>) -> Result<String, OpError> {
+        let vault_id = self.find_id("/v1/vaults", "name", reference.vault).await?;
+        let item_id = self
+            .find_id(
+                &format!("/v1/vaults/{vault_id}/items"),
+                "title",
+                reference.item,
+            )
+            .await?;
+        let item = self
+            .get(&format!("/v1/vaults/{vault_id}/items/{item_id}"))
+            .await?;
+
+        let section_id = match reference.section {
+            Some(section) => Some(
+                find_by(&item, "sections", |entry| {
+                    matches_name(entry, "label", section)
+                })
+                .and_then(|entry| entry.get("id"))
+                .map(Value::to_text)
+                .ok_or_else(|| OpError::NotFound(format!("section {section:?}")))?,
+            ),
+            None => None,
+        };
+
+        find_by(&item, "fields", |entry| {
+            matches_name(entry, "label", reference.field)
+                && section_id.as_deref().is_none_or(|section_id| {
+                    entry.select("section.id").map(Value::to_text).as_deref() == Some(section_id)
+                })
+        })
+        .and_then(|entry| entry.get("value"))
+        .map(Value::to_text)
+        .ok_or_else(|| OpError::NotFound(format!("field {:?}", reference.field)))
+    }
+
+    async fn find_id(&self, path: &str, key: &str, name: &str) -> Result<String, OpError> {
+        let filter = format!("{key} eq \"{name}\"");
+        let filter = utf8_percent_encode(&filter, NON_ALPHANUMERIC);
+        let list = self.get(&format!("{path}?filter={filter}")).await?;
+
+        match &list {
+            Value::Array(entries) => entries.iter().find(|entry| matches_name(entry, key, name)),
+            _ => None,
+        }
+        .and_then(|entry| entry.get("id"))
+        .map(Value::to_text)
+        .ok_or_else(|| OpError::NotFound(format!("{name:?}")))
+    }
+
+    async fn get(&self, path: &str) -> Result<Value, OpError> {
+        let url = format!("{}{path}", self.host);
+        let response = self.client.get(&url, &self.headers, self.timeout).await?;
+
+        if !response.is_success() {
+            return Err(OpError::Status(response.status));
+        }
+
+        response
+            .body
+            .parse()
+            .map_err(|_| OpError::Connect(HttpError::MalformedResponse))
+    }
+}
+
+fn find_by<'v>(
+    value: &'v Value,
+    key: &str,
+    predicate: impl Fn(&Value) -> bool,
+) -> Option<&'v Value> {
+    match value.get(key) {
+        Some(Value::Array(entries)) => entries.iter().find(|entry| predicate(entry)),
+        _ => None,
+    }
+}
+
+fn matches_name(entry: &Value, key: &str, name: &str) -> bool {
+    [key, "id"]
+        .iter()
+        .any(|key| entry.get(key).map(Value::to_text).as_deref() == Some(name))
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for OpCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        self.read(&args)
+            .await
+            .map_err(|err| CommandError(format!("Could not read {args:?}: {err}")))
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn is_secret(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+    use wiremock::{
+        matchers::{header, method, path, query_param, query_param_contains},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    async fn mock_connect() -> MockServer {
+        let server = MockServer::start().await;
+
+        for (route, filter, body) in [
+            (
+                "/v1/vaults",
+                Some("name eq \"prod\""),
+                r#"[{"id":"v1","name":"prod"}]"#,
+            ),
+            (
+                "/v1/vaults/v1/items",
+                Some("title eq \"db\""),
+                r#"[{"id":"i1","title":"db"}]"#,
+            ),
+            (
+                "/v1/vaults/v1/items/i1",
+                None,
+                r#"{"id":"i1","title":"db","sections":[{"id":"s1","label":"replica"}],"fields":[{"id":"password","label":"password","value":"hunter2"},{"id":"f2","label":"password","value":"replica-pass","section":{"id":"s1"}}]}"#,
+            ),
+        ] {
+            let mock = Mock::given(method("GET"))
+                .and(path(route))
+                .and(header("Authorization", "Bearer token"));
+            let mock = match filter {
+                Some(filter) => mock.and(query_param("filter", filter)),
+                None => mock,
+            };
+
+            mock.respond_with(ResponseTemplate::new(200).set_body_string(body))
+                .mount(&server)
+                .await;
+        }
+
+        Mock::given(method("GET"))
+            .and(header("Authorization", "Bearer token"))
+            .and(query_param_contains("filter", "eq"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(header("Authorization", "Bearer token"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("{}"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("{}"))
+            .mount(&server)
+            .await;
+
+        server
+    }
+
+    #[test]
+    fn it_parses_secret_references() {
+        assert_eq!(
+            SecretReference::parse("op://prod/db/password").unwrap(),
+            SecretReference {
+                vault: "prod",
+                item: "db",
+                section: None,
+                field: "password",
+            }
+        );
+        assert_eq!(
+            SecretReference::parse("op://prod/db/replica/password").unwrap(),
+            SecretReference {
+                vault: "prod",
+                item: "db",
+                section: Some("replica"),
+                field: "password",
+            }
+        );
+        assert!(SecretReference::parse("prod/db/password").is_err());
+        assert!(SecretReference::parse("op://prod/db").is_err());
+        assert!(SecretReference::parse("op://prod//password").is_err());
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_from_connect() {
+        let server = mock_connect().await;
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let op_cmd = Arc::new(Mutex::new(OpCommand::connect(server.uri(), "token")));
+        commands.insert("op", op_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${op:op://prod/db/password} ${op:op://prod/db/replica/password}".to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "hunter2 replica-pass".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_on_missing_items() {
+        let server = mock_connect().await;
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let op_cmd = Arc::new(Mutex::new(OpCommand::connect(server.uri(), "token")));
+        let unauthorized_cmd = Arc::new(Mutex::new(OpCommand::connect(server.uri(), "wrong")));
+        commands.insert("op", op_cmd.clone());
+        commands.insert("unauthorized", unauthorized_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        for template in [
+            "${op:op://prod/cache/password:-default_value}",
+            "${op:op://staging/db/password:-default_value}",
+            "${op:op://prod/db/username:-default_value}",
+            "${op:op://prod/db/primary/password:-default_value}",
+            "${op:prod/db/password:-default_value}",
+            "${unauthorized:op://prod/db/password:-default_value}",
+        ] {
+            let result = jakarta.interpolate_string(template.to_owned()).await;
+
+            assert_eq!(result, "default_value".to_owned());
+        }
+    }
+}
@@ -0,0 +1,171 @@
+#[cfg(feature = "sdk")]
+mod sdk;
+
+#[cfg(feature = "sdk")]
+pub use crate::sdk::SdkClient;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use jakarta_json::{Value, ValueExt};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SecretsManagerError {
+    #[error("secret {0:?} not found")]
+    ResourceNotFound(String),
+    #[error("access denied to secret {0:?}")]
+    AccessDenied(String),
+    #[error("secret {0:?} has no string value")]
+    NoStringValue(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Fetches the string value of a secret. [`SdkClient`] talks to AWS, other implementations can
+/// stub it in tests.
+#[async_trait]
+pub trait SecretsManagerClient {
+    async fn get_secret_value(&self, secret_id: &str) -> Result<String, SecretsManagerError>;
+}
+
+pub struct SecretsManagerCommand {
+    client: Box<dyn SecretsManagerClient + Send + Sync>,
+}
+
+impl SecretsManagerCommand {
+    pub fn new(client: impl SecretsManagerClient + Send + Sync + 'static) -> Self {
+        Self {
+            client: Box::new(client),
+        }
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for SecretsManagerCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let secret_id = args.as_str();
+
+        let secret = self
+            .client
+            .get_secret_value(secret_id)
+            .await
+            .map_err(|err| CommandError(format!("Could not get secret {secret_id:?}: {err}")))?;
+
+        let field = match field {
+            Some(field) => field,
+            None => return Ok(secret),
+        };
+
+        secret
+            .parse::<Value>()
+            .ok()
+            .and_then(|value| value.select(&field).map(Value::to_text))
+            .ok_or_else(|| {
+                CommandError(format!("Could not find {field:?} in secret {secret_id:?}"))
+            })
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn is_secret(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    struct StubClient {}
+
+    #[async_trait]
+    impl SecretsManagerClient for StubClient {
+        async fn get_secret_value(&self, secret_id: &str) -> Result<String, SecretsManagerError> {
+            match secret_id {
+                "prod/db/password" => Ok("hunter2".to_owned()),
+                "prod/template" => Ok("pa${ss}word".to_owned()),
+                "prod/db" => Ok(r#"{"username":"admin","password":"hunter2"}"#.to_owned()),
+                "prod/restricted" => Err(SecretsManagerError::AccessDenied(secret_id.to_owned())),
+                _ => Err(SecretsManagerError::ResourceNotFound(secret_id.to_owned())),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_secrets() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sm_cmd = Arc::new(Mutex::new(SecretsManagerCommand::new(StubClient {})));
+        commands.insert("aws-sm", sm_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${aws-sm:prod/db/password}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd hunter2".to_owned());
+
+        let result = jakarta
+            .interpolate_string("asd ${aws-sm:prod/db#username}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd admin".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_on_errors() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sm_cmd = Arc::new(Mutex::new(SecretsManagerCommand::new(StubClient {})));
+        commands.insert("aws-sm", sm_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${aws-sm:prod/missing:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd default_value".to_owned());
+
+        let result = jakarta
+            .interpolate_string("asd ${aws-sm:prod/restricted:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd default_value".to_owned());
+
+        let result = jakarta
+            .interpolate_string("asd ${aws-sm:prod/db#port:-5432}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd 5432".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_does_not_expand_secret_values() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sm_cmd = Arc::new(Mutex::new(SecretsManagerCommand::new(StubClient {})));
+        commands.insert("aws-sm", sm_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${aws-sm:prod/template}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd pa${ss}word".to_owned());
+    }
+}
@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use aws_config::BehaviorVersion;
+use aws_sdk_secretsmanager::{
+    error::{DisplayErrorContext, ProvideErrorMetadata},
+    operation::get_secret_value::GetSecretValueError,
+    Client,
+};
+
+use crate::{SecretsManagerClient, SecretsManagerError};
+
+/// The default [`SecretsManagerClient`], backed by aws-sdk-secretsmanager.
+#[derive(Clone, Debug)]
+pub struct SdkClient {
+    client: Client,
+}
+
+impl SdkClient {
+    /// Resolves region and credentials the way the AWS CLI does, from the environment, the shared
+    /// config files and the instance or container metadata.
+    pub async fn from_env() -> Self {
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+
+        Self::from_client(Client::new(&config))
+    }
+
+    /// Fetches secrets through a preconfigured client, e.g. one assuming a role or talking to a
+    /// custom endpoint.
+    pub fn from_client(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl SecretsManagerClient for SdkClient {
+    async fn get_secret_value(&self, secret_id: &str) -> Result<String, SecretsManagerError> {
+        let output = self
+            .client
+            .get_secret_value()
+            .secret_id(secret_id)
+            .send()
+            .await
+            .map_err(|err| match err.into_service_error() {
+                GetSecretValueError::ResourceNotFoundException(_) => {
+                    SecretsManagerError::ResourceNotFound(secret_id.to_owned())
+                }
+                err if err.code() == Some("AccessDeniedException") => {
+                    SecretsManagerError::AccessDenied(secret_id.to_owned())
+                }
+                err => SecretsManagerError::Other(DisplayErrorContext(err).to_string()),
+            })?;
+
+        output
+            .secret_string
+            .ok_or_else(|| SecretsManagerError::NoStringValue(secret_id.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_secretsmanager::config::{Credentials, Region};
+    use wiremock::{
+        matchers::{body_partial_json, header, method},
+        Mock, MockBuilder, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    async fn client(server: &MockServer) -> SdkClient {
+        let config = aws_sdk_secretsmanager::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .endpoint_url(server.uri())
+            .region(Region::new("eu-central-1"))
+            .credentials_provider(Credentials::for_tests())
+            .build();
+
+        SdkClient::from_client(Client::from_conf(config))
+    }
+
+    fn secret(secret_id: &str) -> MockBuilder {
+        Mock::given(method("POST"))
+            .and(header("x-amz-target", "secretsmanager.GetSecretValue"))
+            .and(body_partial_json(
+                serde_json::json!({ "SecretId": secret_id }),
+            ))
+    }
+
+    #[tokio::test]
+    async fn it_gets_secret_strings() {
+        let server = MockServer::start().await;
+        secret("prod/db")
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "Name": "prod/db", "SecretString": "hunter2" }),
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+
+        assert_eq!(
+            client.get_secret_value("prod/db").await.unwrap(),
+            "hunter2".to_owned()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_maps_service_errors() {
+        let server = MockServer::start().await;
+        secret("prod/missing")
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "__type": "ResourceNotFoundException",
+                "message": "Secrets Manager can't find the specified secret."
+            })))
+            .mount(&server)
+            .await;
+        secret("prod/restricted")
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "__type": "AccessDeniedException",
+                "message": "not authorized"
+            })))
+            .mount(&server)
+            .await;
+        secret("prod/binary")
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "Name": "prod/binary", "SecretBinary": "aGVsbG8=" }),
+            ))
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+
+        assert!(matches!(
+            client.get_secret_value("prod/missing").await,
+            Err(SecretsManagerError::ResourceNotFound(_))
+        ));
+        assert!(matches!(
+            client.get_secret_value("prod/restricted").await,
+            Err(SecretsManagerError::AccessDenied(_))
+        ));
+        assert!(matches!(
+            client.get_secret_value("prod/binary").await,
+            Err(SecretsManagerError::NoStringValue(_))
+        ));
+    }
+}
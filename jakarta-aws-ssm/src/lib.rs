@@ -0,0 +1,193 @@
+#[cfg(feature = "sdk")]
+mod sdk;
+
+#[cfg(feature = "sdk")]
+pub use crate::sdk::SdkClient;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SsmError {
+    #[error("parameter {0:?} not found")]
+    ParameterNotFound(String),
+    #[error("access denied to parameter {0:?}")]
+    AccessDenied(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Looks up a single parameter. [`SdkClient`] is the implementation calling `GetParameter`.
+#[async_trait]
+pub trait SsmClient {
+    async fn get_parameter(&self, name: &str, with_decryption: bool) -> Result<String, SsmError>;
+}
+
+pub struct SsmCommand {
+    client: Box<dyn SsmClient + Send + Sync>,
+    with_decryption: bool,
+}
+
+impl SsmCommand {
+    pub fn new(client: impl SsmClient + Send + Sync + 'static) -> Self {
+        Self {
+            client: Box::new(client),
+            with_decryption: true,
+        }
+    }
+
+    pub fn with_decryption(mut self, with_decryption: bool) -> Self {
+        self.with_decryption = with_decryption;
+        self
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for SsmCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        self.client
+            .get_parameter(&args, self.with_decryption)
+            .await
+            .map_err(|err| CommandError(format!("Could not get parameter {args:?}: {err}")))
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn is_secret(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand, RetryCommand};
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    struct StubClient {}
+
+    #[async_trait]
+    impl SsmClient for StubClient {
+        async fn get_parameter(
+            &self,
+            name: &str,
+            with_decryption: bool,
+        ) -> Result<String, SsmError> {
+            match (name, with_decryption) {
+                ("/myapp/prod/api_key", true) => Ok("api-key".to_owned()),
+                ("/myapp/prod/api_key", false) => Ok("AQICAHh-ciphertext".to_owned()),
+                _ => Err(SsmError::ParameterNotFound(name.to_owned())),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_decrypted_parameters() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let ssm_cmd = Arc::new(Mutex::new(SsmCommand::new(StubClient {})));
+        commands.insert("ssm", ssm_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${ssm:/myapp/prod/api_key}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd api-key".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_passes_the_decryption_flag() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let ssm_cmd = Arc::new(Mutex::new(
+            SsmCommand::new(StubClient {}).with_decryption(false),
+        ));
+        commands.insert("ssm", ssm_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${ssm:/myapp/prod/api_key}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd AQICAHh-ciphertext".to_owned());
+    }
+
+    struct FlakyClient {
+        failures: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SsmClient for FlakyClient {
+        async fn get_parameter(&self, name: &str, _: bool) -> Result<String, SsmError> {
+            let failed = self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |failures| {
+                    failures.checked_sub(1)
+                })
+                .is_ok();
+
+            match failed {
+                true => Err(SsmError::Other("throttled".to_owned())),
+                false => Ok(format!("{name}-value")),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn it_retries_throttled_requests() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let ssm_cmd = Arc::new(Mutex::new(RetryCommand::new(
+            SsmCommand::new(FlakyClient {
+                failures: AtomicUsize::new(2),
+            }),
+            2,
+            Duration::ZERO,
+        )));
+        commands.insert("ssm", ssm_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${ssm:/myapp/prod/api_key}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd /myapp/prod/api_key-value".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_when_not_found() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let ssm_cmd = Arc::new(Mutex::new(SsmCommand::new(StubClient {})));
+        commands.insert("ssm", ssm_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${ssm:/myapp/prod/missing:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd default_value".to_owned());
+    }
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use aws_config::BehaviorVersion;
+use aws_sdk_ssm::{
+    error::{DisplayErrorContext, ProvideErrorMetadata},
+    operation::get_parameter::GetParameterError,
+    Client,
+};
+
+use crate::{SsmClient, SsmError};
+
+/// The default [`SsmClient`], backed by aws-sdk-ssm.
+#[derive(Clone, Debug)]
+pub struct SdkClient {
+    client: Client,
+}
+
+impl SdkClient {
+    /// Resolves region and credentials like the AWS CLI, from the environment, the shared config
+    /// files and the instance or container metadata.
+    pub async fn from_env() -> Self {
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+
+        Self::from_client(Client::new(&config))
+    }
+
+    pub fn from_client(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl SsmClient for SdkClient {
+    async fn get_parameter(&self, name: &str, with_decryption: bool) -> Result<String, SsmError> {
+        let output = self
+            .client
+            .get_parameter()
+            .name(name)
+            .with_decryption(with_decryption)
+            .send()
+            .await
+            .map_err(|err| match err.into_service_error() {
+                GetParameterError::ParameterNotFound(_)
+                | GetParameterError::ParameterVersionNotFound(_) => {
+                    SsmError::ParameterNotFound(name.to_owned())
+                }
+                err if err.code() == Some("AccessDeniedException") => {
+                    SsmError::AccessDenied(name.to_owned())
+                }
+                err => SsmError::Other(DisplayErrorContext(err).to_string()),
+            })?;
+
+        output
+            .parameter
+            .and_then(|parameter| parameter.value)
+            .ok_or_else(|| SsmError::ParameterNotFound(name.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_ssm::config::{Credentials, Region};
+    use wiremock::{
+        matchers::{body_partial_json, header, method},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    fn client(server: &MockServer) -> SdkClient {
+        let config = aws_sdk_ssm::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .endpoint_url(server.uri())
+            .region(Region::new("eu-central-1"))
+            .credentials_provider(Credentials::for_tests())
+            .build();
+
+        SdkClient::from_client(Client::from_conf(config))
+    }
+
+    #[tokio::test]
+    async fn it_gets_decrypted_parameters() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header("x-amz-target", "AmazonSSM.GetParameter"))
+            .and(body_partial_json(
+                serde_json::json!({ "Name": "/prod/db/password", "WithDecryption": true }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Parameter": { "Name": "/prod/db/password", "Type": "SecureString", "Value": "hunter2" }
+            })))
+            .mount(&server)
+            .await;
+
+        assert_eq!(
+            client(&server)
+                .get_parameter("/prod/db/password", true)
+                .await
+                .unwrap(),
+            "hunter2".to_owned()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_maps_service_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(
+                serde_json::json!({ "Name": "/prod/missing" }),
+            ))
+            .respond_with(
+                ResponseTemplate::new(400)
+                    .set_body_json(serde_json::json!({ "__type": "ParameterNotFound" })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(
+                serde_json::json!({ "Name": "/prod/restricted" }),
+            ))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "__type": "AccessDeniedException",
+                "message": "not authorized"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+
+        assert!(matches!(
+            client.get_parameter("/prod/missing", true).await,
+            Err(SsmError::ParameterNotFound(_))
+        ));
+        assert!(matches!(
+            client.get_parameter("/prod/restricted", false).await,
+            Err(SsmError::AccessDenied(_))
+        ));
+    }
+}
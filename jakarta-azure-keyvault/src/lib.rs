@@ -0,0 +1,219 @@
+#[cfg(feature = "sdk")]
+mod sdk;
+
+#[cfg(feature = "sdk")]
+pub use crate::sdk::SdkClient;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AzureKvError {
+    #[error("secret {0:?} not found")]
+    NotFound(String),
+    #[error("access to secret {0:?} is forbidden")]
+    Forbidden(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Gets a secret from the vault at `vault_url`, the latest version unless `version` is given.
+/// [`SdkClient`] is the implementation talking to Key Vault.
+#[async_trait]
+pub trait KeyVaultClient {
+    async fn get_secret(
+        &self,
+        vault_url: &str,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<String, AzureKvError>;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct SecretReference {
+    vault_url: String,
+    name: String,
+    version: Option<String>,
+}
+
+impl SecretReference {
+    fn parse(args: &str, default_vault_url: Option<&str>) -> Option<Self> {
+        let (vault_url, path) = match args.strip_prefix("https://") {
+            Some(rest) => {
+                let (host, path) = rest.split_once('/')?;
+                (format!("https://{host}"), path.strip_prefix("secrets/")?)
+            }
+            None => (default_vault_url?.trim_end_matches('/').to_owned(), args),
+        };
+
+        let mut segments = path.trim_end_matches('/').split('/');
+        let name = segments.next().filter(|name| !name.is_empty())?;
+        let version = segments.next();
+
+        if segments.next().is_some() || version.is_some_and(str::is_empty) {
+            return None;
+        }
+
+        Some(Self {
+            vault_url,
+            name: name.to_owned(),
+            version: version.map(str::to_owned),
+        })
+    }
+}
+
+pub struct AzureKvCommand {
+    client: Box<dyn KeyVaultClient + Send + Sync>,
+    vault_url: Option<String>,
+}
+
+impl AzureKvCommand {
+    pub fn new(client: impl KeyVaultClient + Send + Sync + 'static) -> Self {
+        Self {
+            client: Box::new(client),
+            vault_url: None,
+        }
+    }
+
+    pub fn with_vault_url(mut self, vault_url: impl Into<String>) -> Self {
+        self.vault_url = Some(vault_url.into());
+        self
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for AzureKvCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let reference =
+            SecretReference::parse(&args, self.vault_url.as_deref()).ok_or_else(|| {
+                CommandError(format!(
+                    "Invalid secret reference {args:?}, expected https://<vault>/secrets/<name>[/<version>]"
+                ))
+            })?;
+
+        self.client
+            .get_secret(
+                &reference.vault_url,
+                &reference.name,
+                reference.version.as_deref(),
+            )
+            .await
+            .map_err(|err| CommandError(format!("Could not get secret {args:?}: {err}")))
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn is_secret(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    struct StubClient {}
+
+    #[async_trait]
+    impl KeyVaultClient for StubClient {
+        async fn get_secret(
+            &self,
+            vault_url: &str,
+            name: &str,
+            version: Option<&str>,
+        ) -> Result<String, AzureKvError> {
+            match (vault_url, name, version) {
+                ("https://myvault.vault.azure.net", "api-key", None) => Ok("latest-key".to_owned()),
+                ("https://myvault.vault.azure.net", "api-key", Some("abc123")) => {
+                    Ok("old-key".to_owned())
+                }
+                (_, "restricted", _) => Err(AzureKvError::Forbidden(name.to_owned())),
+                _ => Err(AzureKvError::NotFound(name.to_owned())),
+            }
+        }
+    }
+
+    #[test]
+    fn it_parses_secret_references() {
+        assert_eq!(
+            SecretReference::parse("https://myvault.vault.azure.net/secrets/api-key", None),
+            Some(SecretReference {
+                vault_url: "https://myvault.vault.azure.net".to_owned(),
+                name: "api-key".to_owned(),
+                version: None,
+            })
+        );
+        assert_eq!(
+            SecretReference::parse("api-key/abc123", Some("https://myvault.vault.azure.net/")),
+            Some(SecretReference {
+                vault_url: "https://myvault.vault.azure.net".to_owned(),
+                name: "api-key".to_owned(),
+                version: Some("abc123".to_owned()),
+            })
+        );
+        assert_eq!(SecretReference::parse("api-key", None), None);
+        assert_eq!(
+            SecretReference::parse("https://myvault.vault.azure.net/keys/api-key", None),
+            None
+        );
+        assert_eq!(
+            SecretReference::parse("https://myvault.vault.azure.net/secrets/a/b/c", None),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_secrets() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let kv_cmd = Arc::new(Mutex::new(
+            AzureKvCommand::new(StubClient {}).with_vault_url("https://myvault.vault.azure.net"),
+        ));
+        commands.insert("azure-kv", kv_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${azure-kv:https://myvault.vault.azure.net/secrets/api-key} ${azure-kv:api-key/abc123}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "latest-key old-key".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_on_errors() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let kv_cmd = Arc::new(Mutex::new(AzureKvCommand::new(StubClient {})));
+        commands.insert("azure-kv", kv_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        for template in [
+            "${azure-kv:https://myvault.vault.azure.net/secrets/missing:-default_value}",
+            "${azure-kv:https://myvault.vault.azure.net/secrets/restricted:-default_value}",
+            "${azure-kv:api-key:-default_value}",
+        ] {
+            let result = jakarta.interpolate_string(template.to_owned()).await;
+
+            assert_eq!(result, "default_value".to_owned());
+        }
+    }
+}
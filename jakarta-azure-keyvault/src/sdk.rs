@@ -0,0 +1,185 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use azure_core::{auth::TokenCredential, error::ErrorKind, StatusCode};
+use azure_security_keyvault::SecretClient;
+
+use crate::{AzureKvError, KeyVaultClient};
+
+/// The default [`KeyVaultClient`], backed by azure_security_keyvault. Keeps one `SecretClient`
+/// per vault, all sharing the credential and its token cache.
+pub struct SdkClient {
+    credential: Arc<dyn TokenCredential>,
+    clients: Mutex<HashMap<String, SecretClient>>,
+}
+
+impl SdkClient {
+    /// Authenticates with a `DefaultAzureCredential`, trying the environment, managed identity
+    /// and the Azure CLI in that order.
+    pub fn from_env() -> Result<Self, AzureKvError> {
+        let credential = azure_identity::create_default_credential()
+            .map_err(|err| AzureKvError::Other(err.to_string()))?;
+
+        Ok(Self::new(credential))
+    }
+
+    pub fn new(credential: Arc<dyn TokenCredential>) -> Self {
+        Self {
+            credential,
+            clients: Mutex::default(),
+        }
+    }
+
+    fn client(&self, vault_url: &str) -> Result<SecretClient, AzureKvError> {
+        let mut clients = self
+            .clients
+            .lock()
+            .map_err(|err| AzureKvError::Other(err.to_string()))?;
+
+        if let Some(client) = clients.get(vault_url) {
+            return Ok(client.clone());
+        }
+
+        let client = SecretClient::new(vault_url, self.credential.clone())
+            .map_err(|err| AzureKvError::Other(err.to_string()))?;
+        clients.insert(vault_url.to_owned(), client.clone());
+
+        Ok(client)
+    }
+}
+
+#[async_trait]
+impl KeyVaultClient for SdkClient {
+    async fn get_secret(
+        &self,
+        vault_url: &str,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<String, AzureKvError> {
+        let request = self.client(vault_url)?.get(name);
+        let request = match version {
+            Some(version) => request.version(version),
+            None => request,
+        };
+
+        let secret = request.await.map_err(|err| match err.kind() {
+            ErrorKind::HttpResponse {
+                status: StatusCode::NotFound,
+                ..
+            } => AzureKvError::NotFound(name.to_owned()),
+            ErrorKind::HttpResponse {
+                status: StatusCode::Unauthorized | StatusCode::Forbidden,
+                ..
+            } => AzureKvError::Forbidden(name.to_owned()),
+            _ => AzureKvError::Other(err.to_string()),
+        })?;
+
+        Ok(secret.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use azure_core::auth::AccessToken;
+    use time::{Duration, OffsetDateTime};
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct StaticCredential {}
+
+    #[async_trait]
+    impl TokenCredential for StaticCredential {
+        async fn get_token(&self, _: &[&str]) -> azure_core::Result<AccessToken> {
+            Ok(AccessToken::new(
+                "token",
+                OffsetDateTime::now_utc() + Duration::hours(1),
+            ))
+        }
+
+        async fn clear_cache(&self) -> azure_core::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn secret(value: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": value,
+            "id": "https://myvault.vault.azure.net/secrets/api-key/abc123",
+            "attributes": {
+                "enabled": true,
+                "created": 1700000000,
+                "updated": 1700000000,
+                "recoveryLevel": "Recoverable"
+            }
+        }))
+    }
+
+    #[tokio::test]
+    async fn it_gets_secret_versions() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/secrets/api-key/"))
+            .and(header("authorization", "Bearer token"))
+            .respond_with(secret("latest-key"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/secrets/api-key/abc123"))
+            .respond_with(secret("old-key"))
+            .mount(&server)
+            .await;
+
+        let client = SdkClient::new(Arc::new(StaticCredential {}));
+
+        assert_eq!(
+            client
+                .get_secret(&server.uri(), "api-key", None)
+                .await
+                .unwrap(),
+            "latest-key".to_owned()
+        );
+        assert_eq!(
+            client
+                .get_secret(&server.uri(), "api-key", Some("abc123"))
+                .await
+                .unwrap(),
+            "old-key".to_owned()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_maps_status_codes() {
+        let server = MockServer::start().await;
+        Mock::given(path("/secrets/missing/"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "error": { "code": "SecretNotFound", "message": "missing" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(path("/secrets/restricted/"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "error": { "code": "Forbidden", "message": "restricted" }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = SdkClient::new(Arc::new(StaticCredential {}));
+
+        assert!(matches!(
+            client.get_secret(&server.uri(), "missing", None).await,
+            Err(AzureKvError::NotFound(_))
+        ));
+        assert!(matches!(
+            client.get_secret(&server.uri(), "restricted", None).await,
+            Err(AzureKvError::Forbidden(_))
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use jakarta::{CommandError, Context};
+
+/// Encodes or decodes the `#field` payload, e.g. `${base64:encode#${env:SECRET}}`.
+pub struct Base64Command {}
+
+#[async_trait]
+impl jakarta::JakartaCommand for Base64Command {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let payload = field.unwrap_or_default();
+
+        let value = match args.trim() {
+            "encode" => Some(STANDARD.encode(&payload)),
+            "decode" => STANDARD
+                .decode(payload.trim())
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok()),
+            mode => return Err(CommandError(format!("Unknown base64 mode {mode:?}"))),
+        };
+
+        value.ok_or_else(|| CommandError(format!("Could not decode base64 payload {payload:?}")))
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_round_trips_values() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let base64_cmd = Arc::new(Mutex::new(Base64Command {}));
+        commands.insert("base64", base64_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${base64:encode#user:p@ss}".to_owned())
+            .await;
+
+        assert_eq!(result, "dXNlcjpwQHNz".to_owned());
+
+        let result = jakarta
+            .interpolate_string("${base64:decode#${base64:encode#hello world}}".to_owned())
+            .await;
+
+        assert_eq!(result, "hello world".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_on_invalid_input() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let base64_cmd = Arc::new(Mutex::new(Base64Command {}));
+        commands.insert("base64", base64_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${base64:decode#not base64!:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "default_value".to_owned());
+
+        let result = jakarta
+            .interpolate_string("${base64:rot13#abc:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "default_value".to_owned());
+    }
+}
@@ -0,0 +1,196 @@
+mod toml;
+mod yaml;
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use jakarta_json::{Value, ValueExt};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("failed to read config file")]
+    Io(#[from] std::io::Error),
+    #[error("invalid JSON: {0}")]
+    Json(#[from] jakarta_json::ParseError),
+    #[error("invalid YAML on line {0}: {1}")]
+    Yaml(usize, String),
+    #[error("invalid TOML on line {0}: {1}")]
+    Toml(usize, String),
+    #[error("unknown config format for {0:?}")]
+    UnknownFormat(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl Format {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "yaml" | "yml" => Some(Format::Yaml),
+            "toml" => Some(Format::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Resolves `${cfg:server.ports.0}` against a document loaded once from JSON, YAML or TOML.
+/// Paths that are missing or point at arrays or maps resolve to the default value.
+pub struct ConfigCommand {
+    document: Value,
+}
+
+impl ConfigCommand {
+    pub fn new(document: Value) -> Self {
+        Self { document }
+    }
+
+    pub fn parse(document: &str, format: Format) -> Result<Self, ConfigError> {
+        let document = match format {
+            Format::Json => document.parse()?,
+            Format::Yaml => yaml::parse(document)?,
+            Format::Toml => toml::parse(document)?,
+        };
+
+        Ok(Self::new(document))
+    }
+
+    /// Loads `path`, picking the format from its extension.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let format = path
+            .extension()
+            .and_then(|extension| Format::from_extension(&extension.to_string_lossy()))
+            .ok_or_else(|| ConfigError::UnknownFormat(path.display().to_string()))?;
+
+        Self::parse(&std::fs::read_to_string(path)?, format)
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for ConfigCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        match self.document.select(&args) {
+            Some(Value::Array(_) | Value::Object(_)) => {
+                Err(CommandError(format!("Config path {args:?} is not a leaf")))
+            }
+            Some(value) => Ok(value.to_text()),
+            None => Err(CommandError(format!("Could not find config path {args:?}"))),
+        }
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    const JSON: &str = r#"{
+        "server": {"host": "localhost", "ports": [8080, 8443], "tls": true},
+        "users": [{"name": "alice"}, {"name": "bob", "admin": null}]
+    }"#;
+
+    const YAML: &str = r#"
+server:
+  host: localhost # the bind address
+  ports:
+    - 8080
+    - 8443
+  tls: true
+users:
+  - name: alice
+  - name: "bob"
+    admin: ~
+"#;
+
+    const TOML: &str = r#"
+[server]
+host = "localhost"
+ports = [8080, 8443]
+tls = true
+
+[[users]]
+name = "alice"
+
+[[users]]
+name = 'bob'
+"#;
+
+    #[tokio::test]
+    async fn it_resolves_paths_in_every_format() {
+        for (document, format) in [
+            (JSON, Format::Json),
+            (YAML, Format::Yaml),
+            (TOML, Format::Toml),
+        ] {
+            let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+            let cfg_cmd = ConfigCommand::parse(document, format).unwrap();
+            commands.insert("cfg", Arc::new(Mutex::new(cfg_cmd)));
+            let jakarta = Jakarta::new(commands).unwrap();
+
+            let result = jakarta
+                .interpolate_string(
+                    "${cfg:server.host}:${cfg:server.ports.1} ${cfg:server.tls} ${cfg:users.1.name} [${cfg:users.0.admin:-none}]"
+                        .to_owned(),
+                )
+                .await;
+
+            assert_eq!(
+                result,
+                "localhost:8443 true bob [none]".to_owned(),
+                "{format:?}"
+            );
+
+            let result = jakarta
+                .interpolate_string(
+                    "${cfg:server:-map} ${cfg:server.ports:-list} ${cfg:server.ports.2:-missing}"
+                        .to_owned(),
+                )
+                .await;
+
+            assert_eq!(result, "map list missing".to_owned(), "{format:?}");
+        }
+    }
+
+    #[test]
+    fn it_picks_the_format_from_the_extension() {
+        let path = std::env::temp_dir().join("jakarta-config-it-picks-the-format.yml");
+        std::fs::write(&path, "a:\n  b: c\n").unwrap();
+
+        let cfg_cmd = ConfigCommand::from_file(&path).unwrap();
+        assert_eq!(
+            cfg_cmd.document.select("a.b"),
+            Some(&Value::String("c".to_owned()))
+        );
+
+        assert!(matches!(
+            ConfigCommand::from_file("config.ini"),
+            Err(ConfigError::UnknownFormat(_))
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,139 @@
+//! Loads TOML config files through the toml crate, converting the document into the same
+//! [`Value`] tree as the JSON and YAML loaders. Dates and times are kept as strings.
+
+use jakarta_json::Value;
+use toml::Value as Toml;
+
+use crate::ConfigError;
+
+pub(crate) fn parse(document: &str) -> Result<Value, ConfigError> {
+    let table = document.parse::<toml::Table>().map_err(|err| {
+        let line = err.span().map_or(0, |span| {
+            document[..span.start.min(document.len())]
+                .matches('\n')
+                .count()
+                + 1
+        });
+
+        ConfigError::Toml(line, err.message().to_owned())
+    })?;
+
+    Ok(convert(Toml::Table(table)))
+}
+
+fn convert(value: Toml) -> Value {
+    match value {
+        Toml::String(value) => Value::String(value),
+        Toml::Integer(value) => Value::from(value),
+        Toml::Float(value) => Value::from(value),
+        Toml::Boolean(value) => Value::Bool(value),
+        Toml::Datetime(value) => Value::String(value.to_string()),
+        Toml::Array(values) => Value::Array(values.into_iter().map(convert).collect()),
+        Toml::Table(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key, convert(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta_json::ValueExt;
+
+    use super::*;
+
+    #[test]
+    fn it_parses_tables_and_values() {
+        let value = parse(
+            r#"
+title = "TOML \"example\"" # comment
+"quoted key" = 'C:\path'
+dotted.key = 0xff
+
+[owner]
+name = """
+multi
+line"""
+dob = 1979-05-27T07:32:00-08:00
+
+[database.connection]
+ports = [
+  8000,
+  8_001, # trailing comma
+]
+ratio = +1.5e3
+enabled = false
+inline = { user = "admin", nested.level = 2 }
+
+[[products]]
+name = "Hammer"
+
+[[products]]
+name = "Nail"
+sku = 284_758_393
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            value.select("title").unwrap().to_text(),
+            r#"TOML "example""#
+        );
+        assert_eq!(value.select("quoted key").unwrap().to_text(), r"C:\path");
+        assert_eq!(value.select("dotted.key").unwrap().to_text(), "255");
+        assert_eq!(value.select("owner.name").unwrap().to_text(), "multi\nline");
+        assert_eq!(
+            value.select("owner.dob").unwrap().to_text(),
+            "1979-05-27T07:32:00-08:00"
+        );
+        assert_eq!(
+            value
+                .select("database.connection.ports.1")
+                .unwrap()
+                .to_text(),
+            "8001"
+        );
+        assert_eq!(
+            value.select("database.connection.ratio").unwrap().to_text(),
+            "1500.0"
+        );
+        assert_eq!(
+            value.select("database.connection.enabled"),
+            Some(&Value::Bool(false))
+        );
+        assert_eq!(
+            value
+                .select("database.connection.inline.nested.level")
+                .unwrap()
+                .to_text(),
+            "2"
+        );
+        assert_eq!(
+            value.select("products.1.sku").unwrap().to_text(),
+            "284758393"
+        );
+    }
+
+    #[test]
+    fn it_rejects_malformed_documents() {
+        assert!(matches!(
+            parse("a = 1\na = 2\n"),
+            Err(ConfigError::Toml(2, _))
+        ));
+        assert!(matches!(
+            parse("a = \"open\n"),
+            Err(ConfigError::Toml(_, _))
+        ));
+        assert!(matches!(
+            parse("\n\na = [1 2]\n"),
+            Err(ConfigError::Toml(3, _))
+        ));
+        assert!(matches!(
+            parse("a = 1 b = 2\n"),
+            Err(ConfigError::Toml(1, _))
+        ));
+        assert!(matches!(parse("a = nope\n"), Err(ConfigError::Toml(1, _))));
+    }
+}
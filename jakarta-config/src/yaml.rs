@@ -0,0 +1,131 @@
+//! Loads YAML config files through serde_yaml, converting the document into the same [`Value`]
+//! tree as the JSON and TOML loaders. Merge keys are applied and tags are dropped.
+
+use jakarta_json::Value;
+use serde_yaml::Value as Yaml;
+
+use crate::ConfigError;
+
+pub(crate) fn parse(document: &str) -> Result<Value, ConfigError> {
+    let mut value = serde_yaml::from_str::<Yaml>(document).map_err(error)?;
+    value.apply_merge().map_err(error)?;
+
+    convert(value)
+}
+
+fn convert(value: Yaml) -> Result<Value, ConfigError> {
+    Ok(match value {
+        Yaml::Null => Value::Null,
+        Yaml::Bool(value) => Value::Bool(value),
+        Yaml::Number(value) => match (value.as_i64(), value.as_u64()) {
+            (Some(value), _) => Value::from(value),
+            (_, Some(value)) => Value::from(value),
+            _ => Value::from(value.as_f64()),
+        },
+        Yaml::String(value) => Value::String(value),
+        Yaml::Sequence(values) => {
+            Value::Array(values.into_iter().map(convert).collect::<Result<_, _>>()?)
+        }
+        Yaml::Mapping(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| Ok((key_text(key)?, convert(value)?)))
+                .collect::<Result<_, ConfigError>>()?,
+        ),
+        Yaml::Tagged(tagged) => convert(tagged.value)?,
+    })
+}
+
+fn key_text(key: Yaml) -> Result<String, ConfigError> {
+    match key {
+        Yaml::String(key) => Ok(key),
+        Yaml::Null => Ok("null".to_owned()),
+        Yaml::Bool(key) => Ok(key.to_string()),
+        Yaml::Number(key) => Ok(key.to_string()),
+        Yaml::Tagged(tagged) => key_text(tagged.value),
+        Yaml::Sequence(_) | Yaml::Mapping(_) => Err(ConfigError::Yaml(
+            0,
+            "collections can't be used as mapping keys".to_owned(),
+        )),
+    }
+}
+
+fn error(err: serde_yaml::Error) -> ConfigError {
+    let line = err.location().map_or(0, |location| location.line());
+
+    ConfigError::Yaml(line, err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta_json::ValueExt;
+
+    use super::*;
+
+    #[test]
+    fn it_parses_nested_collections() {
+        let value = parse(
+            r#"
+# services
+name: app
+url: http://example.com:8080/path # trailing
+defaults: &defaults
+  LEVEL: 'it''s'
+services:
+- name: web
+  ports: [80, "443"]
+  env: {DEBUG: "false", <<: *defaults}
+-
+  name: worker
+  replicas: -2
+matrix:
+  - - a
+    - b
+  - [c]
+script: |
+  echo one
+  echo two
+summary: >-
+  folded
+  text
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(value.select("name").unwrap().to_text(), "app");
+        assert_eq!(
+            value.select("url").unwrap().to_text(),
+            "http://example.com:8080/path"
+        );
+        assert_eq!(value.select("services.0.ports.1").unwrap().to_text(), "443");
+        assert_eq!(
+            value.select("services.0.env.LEVEL").unwrap().to_text(),
+            "it's"
+        );
+        assert_eq!(value.select("services.1.replicas"), Some(&Value::from(-2)));
+        assert_eq!(value.select("matrix.0.1").unwrap().to_text(), "b");
+        assert_eq!(value.select("matrix.1.0").unwrap().to_text(), "c");
+        assert_eq!(
+            value.select("script").unwrap().to_text(),
+            "echo one\necho two\n"
+        );
+        assert_eq!(value.select("summary").unwrap().to_text(), "folded text");
+    }
+
+    #[test]
+    fn it_rejects_malformed_documents() {
+        assert!(matches!(
+            parse("a: 1\n    b: 2\n"),
+            Err(ConfigError::Yaml(2, _))
+        ));
+        assert!(matches!(parse("a: [1, 2\n"), Err(ConfigError::Yaml(_, _))));
+        assert!(matches!(
+            parse("a:\n  - 1\n  b: 2\n"),
+            Err(ConfigError::Yaml(3, _))
+        ));
+        assert!(matches!(
+            parse("? [a]\n: 1\n"),
+            Err(ConfigError::Yaml(_, _))
+        ));
+    }
+}
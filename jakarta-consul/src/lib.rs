@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use jakarta_http::{HttpClient, ReqwestClient};
+use jakarta_json::{Value, ValueExt};
+
+pub struct ConsulCommand {
+    client: Box<dyn HttpClient + Send + Sync>,
+    address: String,
+    token: Option<String>,
+    timeout: Duration,
+}
+
+impl ConsulCommand {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            client: Box::new(ReqwestClient::new()),
+            address: address.into().trim_end_matches('/').to_owned(),
+            token: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    pub fn with_client(mut self, client: impl HttpClient + Send + Sync + 'static) -> Self {
+        self.client = Box::new(client);
+        self
+    }
+
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for ConsulCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let url = format!(
+            "{}/v1/kv/{}?raw",
+            self.address,
+            args.trim_start_matches('/')
+        );
+        let headers = match &self.token {
+            Some(token) => vec![("X-Consul-Token".to_owned(), token.clone())],
+            None => vec![],
+        };
+
+        let response = match self.client.get(&url, &headers, self.timeout).await {
+            Ok(response) if response.status == 200 => response,
+            Ok(response) => {
+                return Err(CommandError(format!(
+                    "Could not get key {args:?} from consul, status {}",
+                    response.status
+                )))
+            }
+            Err(err) => {
+                return Err(CommandError(format!(
+                    "Could not get key {args:?} from consul: {err}"
+                )))
+            }
+        };
+
+        let field = match field {
+            Some(field) => field,
+            None => return Ok(response.body),
+        };
+
+        response
+            .body
+            .parse::<Value>()
+            .ok()
+            .and_then(|value| value.select(&field).map(Value::to_text))
+            .ok_or_else(|| CommandError(format!("Could not find {field:?} in consul key {args:?}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+    use wiremock::{
+        matchers::{header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    async fn mock_consul() -> MockServer {
+        let server = MockServer::start().await;
+
+        for (key, body) in [
+            ("timeout", "30s"),
+            ("db", r#"{"host":"db.internal","port":5432}"#),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/kv/config/service/{key}")))
+                .and(query_param("raw", ""))
+                .and(header("X-Consul-Token", "token"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(body))
+                .mount(&server)
+                .await;
+        }
+
+        Mock::given(method("GET"))
+            .and(header("X-Consul-Token", "token"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("ACL not found"))
+            .mount(&server)
+            .await;
+
+        server
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_raw_values() {
+        let server = mock_consul().await;
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let consul_cmd = Arc::new(Mutex::new(
+            ConsulCommand::new(format!("{}/", server.uri())).with_token("token"),
+        ));
+        commands.insert("consul", consul_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("timeout=${consul:config/service/timeout}".to_owned())
+            .await;
+
+        assert_eq!(result, "timeout=30s".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_json_fields() {
+        let server = mock_consul().await;
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let consul_cmd = Arc::new(Mutex::new(
+            ConsulCommand::new(server.uri()).with_token("token"),
+        ));
+        commands.insert("consul", consul_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${consul:config/service/db#host}:${consul:config/service/db#port}".to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "db.internal:5432".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_for_missing_keys() {
+        let server = mock_consul().await;
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let consul_cmd = Arc::new(Mutex::new(
+            ConsulCommand::new(server.uri()).with_token("token"),
+        ));
+        let anonymous_cmd = Arc::new(Mutex::new(ConsulCommand::new(server.uri())));
+        commands.insert("consul", consul_cmd.clone());
+        commands.insert("anonymous", anonymous_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        for template in [
+            "${consul:config/missing:-default_value}",
+            "${consul:config/service/db#user:-default_value}",
+            "${anonymous:config/service/timeout:-default_value}",
+        ] {
+            let result = jakarta.interpolate_string(template.to_owned()).await;
+
+            assert_eq!(result, "default_value".to_owned());
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::{fmt::Display, time::SystemTime};
+
+use async_trait::async_trait;
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, Local, TimeZone, Utc,
+};
+use jakarta::{CommandError, Context};
+
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+pub struct SystemClock {}
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+pub struct DateCommand {
+    clock: Box<dyn Clock + Send + Sync>,
+}
+
+impl DateCommand {
+    pub fn new() -> Self {
+        Self {
+            clock: Box::new(SystemClock {}),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: impl Clock + Send + Sync + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+}
+
+impl Default for DateCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn format_date<Tz: TimeZone>(date: DateTime<Tz>, format: &str) -> Option<String>
+where
+    Tz::Offset: Display,
+{
+    let items = StrftimeItems::new(format).collect::<Vec<_>>();
+
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return None;
+    }
+
+    Some(date.format_with_items(items.iter()).to_string())
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for DateCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let format = args.as_str();
+        let timezone = field.as_deref().unwrap_or("local");
+
+        let now = DateTime::<Utc>::from(self.clock.now());
+
+        let formatted = match timezone {
+            "utc" => format_date(now, format),
+            "local" => format_date(now.with_timezone(&Local), format),
+            timezone => return Err(CommandError(format!("Unsupported timezone {timezone:?}"))),
+        };
+
+        formatted.ok_or_else(|| CommandError(format!("Invalid date format {format:?}")))
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{
+        collections::HashMap,
+        sync::Arc,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    struct FixedClock {}
+
+    impl Clock for FixedClock {
+        fn now(&self) -> SystemTime {
+            UNIX_EPOCH + Duration::from_secs(1700000000)
+        }
+    }
+
+    struct TickingClock {
+        ticks: std::sync::atomic::AtomicU64,
+    }
+
+    impl Clock for TickingClock {
+        fn now(&self) -> SystemTime {
+            let tick = self.ticks.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+            UNIX_EPOCH + Duration::from_secs(1700000000 + tick)
+        }
+    }
+
+    #[tokio::test]
+    async fn it_reads_the_clock_for_every_placeholder() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let date_cmd = Arc::new(Mutex::new(DateCommand::new().with_clock(TickingClock {
+            ticks: Default::default(),
+        })));
+        commands.insert("date", date_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${date:%s#utc} ${date:%s#utc} ${date:%s#utc}".to_owned())
+            .await;
+
+        assert_eq!(result, "1700000000 1700000001 1700000002".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_formats_dates_in_utc() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let date_cmd = Arc::new(Mutex::new(DateCommand::new().with_clock(FixedClock {})));
+        commands.insert("date", date_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${date:%Y-%m-%d#utc} ${date:%Y-%m-%dT%H:%M:%SZ#utc}".to_owned())
+            .await;
+
+        assert_eq!(result, "2023-11-14 2023-11-14T22:13:20Z".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_formats_dates_in_local_time() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let date_cmd = Arc::new(Mutex::new(DateCommand::new().with_clock(FixedClock {})));
+        commands.insert("date", date_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta.interpolate_string("${date:%s %z}".to_owned()).await;
+        let offset = Local
+            .timestamp_opt(1700000000, 0)
+            .unwrap()
+            .offset()
+            .local_minus_utc();
+
+        assert_eq!(
+            result,
+            format!(
+                "1700000000 {}{:02}{:02}",
+                if offset < 0 { '-' } else { '+' },
+                offset.abs() / 3600,
+                offset.abs() / 60 % 60
+            )
+        );
+    }
+
+    #[test]
+    fn it_formats_specifiers() {
+        let date = chrono::FixedOffset::east_opt(3600 + 1800)
+            .unwrap()
+            .timestamp_opt(1700000000, 0)
+            .unwrap();
+
+        assert_eq!(
+            format_date(date, "%F %T %z %a %b %j %I%p %%").unwrap(),
+            "2023-11-14 23:43:20 +0130 Tue Nov 318 11PM %"
+        );
+        assert_eq!(format_date(date, "%Q"), None);
+        assert_eq!(format_date(date, "%"), None);
+
+        let date = Utc.timestamp_opt(-1, 0).unwrap();
+
+        assert_eq!(format_date(date, "%F %T").unwrap(), "1969-12-31 23:59:59");
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_for_invalid_formats() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let date_cmd = Arc::new(Mutex::new(DateCommand::new().with_clock(FixedClock {})));
+        commands.insert("date", date_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${date:%Q#utc:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "default_value".to_owned());
+
+        let result = jakarta
+            .interpolate_string("${date:%Y#mars:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "default_value".to_owned());
+    }
+}
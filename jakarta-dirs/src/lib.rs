@@ -0,0 +1,275 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+
+type EnvLookup = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Linux,
+    MacOs,
+    Windows,
+}
+
+impl Platform {
+    pub fn current() -> Self {
+        if cfg!(target_os = "windows") {
+            Platform::Windows
+        } else if cfg!(target_os = "macos") {
+            Platform::MacOs
+        } else {
+            Platform::Linux
+        }
+    }
+
+    fn is_absolute(&self, path: &str) -> bool {
+        match self {
+            Platform::Windows => {
+                let bytes = path.as_bytes();
+
+                path.starts_with(r"\\")
+                    || (bytes.len() > 2
+                        && bytes[0].is_ascii_alphabetic()
+                        && bytes[1] == b':'
+                        && matches!(bytes[2], b'\\' | b'/'))
+            }
+            Platform::Linux | Platform::MacOs => path.starts_with('/'),
+        }
+    }
+
+    fn join(&self, base: &str, parts: &[&str]) -> String {
+        let separator = match self {
+            Platform::Windows => '\\',
+            Platform::Linux | Platform::MacOs => '/',
+        };
+
+        parts.iter().fold(
+            base.trim_end_matches(separator).to_owned(),
+            |mut path, part| {
+                path.push(separator);
+                path.push_str(part);
+                path
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Home,
+    Config,
+    Data,
+    Cache,
+}
+
+impl Kind {
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "home" => Some(Kind::Home),
+            "config" | "config-dir" => Some(Kind::Config),
+            "data" | "data-dir" => Some(Kind::Data),
+            "cache" | "cache-dir" => Some(Kind::Cache),
+            _ => None,
+        }
+    }
+}
+
+/// Resolves `${home}`, `${config-dir}`, `${data-dir}` and `${cache-dir}`, or `${dirs:config}`
+/// and friends, to the absolute per-user directory of the current OS: the XDG base directories
+/// on Linux, `~/Library` on macOS and the known folders on Windows.
+pub struct DirsCommand {
+    platform: Platform,
+    env: EnvLookup,
+}
+
+impl DirsCommand {
+    pub fn new() -> Self {
+        Self {
+            platform: Platform::current(),
+            env: Arc::new(|name| std::env::var(name).ok()),
+        }
+    }
+
+    pub fn with_platform(mut self, platform: Platform) -> Self {
+        self.platform = platform;
+        self
+    }
+
+    /// Looks up environment variables through `env` instead of the process environment.
+    pub fn with_env(
+        mut self,
+        env: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.env = Arc::new(env);
+        self
+    }
+
+    /// Reads an environment variable holding an absolute path, ignoring empty or relative ones.
+    fn absolute_var(&self, name: &str) -> Option<String> {
+        (self.env)(name).filter(|path| self.platform.is_absolute(path))
+    }
+
+    fn resolve(&self, kind: Kind) -> Option<String> {
+        let platform = self.platform;
+
+        match platform {
+            Platform::Linux | Platform::MacOs => {
+                let home = self.absolute_var("HOME")?;
+
+                match (platform, kind) {
+                    (_, Kind::Home) => Some(home),
+                    (Platform::MacOs, Kind::Config | Kind::Data) => {
+                        Some(platform.join(&home, &["Library", "Application Support"]))
+                    }
+                    (Platform::MacOs, Kind::Cache) => {
+                        Some(platform.join(&home, &["Library", "Caches"]))
+                    }
+                    (_, Kind::Config) => self
+                        .absolute_var("XDG_CONFIG_HOME")
+                        .or_else(|| Some(platform.join(&home, &[".config"]))),
+                    (_, Kind::Data) => self
+                        .absolute_var("XDG_DATA_HOME")
+                        .or_else(|| Some(platform.join(&home, &[".local", "share"]))),
+                    (_, Kind::Cache) => self
+                        .absolute_var("XDG_CACHE_HOME")
+                        .or_else(|| Some(platform.join(&home, &[".cache"]))),
+                }
+            }
+            Platform::Windows => match kind {
+                Kind::Home => self.absolute_var("USERPROFILE"),
+                Kind::Config | Kind::Data => self.absolute_var("APPDATA"),
+                Kind::Cache => self.absolute_var("LOCALAPPDATA"),
+            },
+        }
+    }
+}
+
+impl Default for DirsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for DirsCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        command: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let kind = match args.trim() {
+            "" => command.as_str(),
+            args => args,
+        };
+
+        let Some(kind) = Kind::parse(kind) else {
+            return Err(CommandError(format!("Unknown directory {kind:?}")));
+        };
+
+        self.resolve(kind)
+            .ok_or_else(|| CommandError(format!("Could not find the {kind:?} directory")))
+    }
+
+    fn identifiers(&self) -> Vec<&'static str> {
+        vec!["dirs", "home", "config-dir", "data-dir", "cache-dir"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaBuilder, JakartaCommand};
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn dirs_cmd(platform: Platform, env: &[(&'static str, &'static str)]) -> DirsCommand {
+        let env = env.iter().copied().collect::<HashMap<_, _>>();
+
+        DirsCommand::new()
+            .with_platform(platform)
+            .with_env(move |name| env.get(name).map(|value| value.to_string()))
+    }
+
+    async fn interpolate(dirs_cmd: DirsCommand, template: &str) -> String {
+        let jakarta = JakartaBuilder::new()
+            .register_auto(dirs_cmd)
+            .build()
+            .unwrap();
+
+        jakarta.interpolate_string(template.to_owned()).await
+    }
+
+    #[tokio::test]
+    async fn it_resolves_absolute_paths_on_each_platform() {
+        let template = "${home}|${config-dir}|${data-dir}|${dirs:cache}";
+
+        let linux = dirs_cmd(
+            Platform::Linux,
+            &[
+                ("HOME", "/home/me"),
+                ("XDG_CONFIG_HOME", "/etc/me"),
+                ("XDG_CACHE_HOME", "tmp"),
+            ],
+        );
+        assert_eq!(
+            interpolate(linux, template).await,
+            "/home/me|/etc/me|/home/me/.local/share|/home/me/.cache"
+        );
+
+        let macos = dirs_cmd(Platform::MacOs, &[("HOME", "/Users/me/")]);
+        assert_eq!(
+            interpolate(macos, template).await,
+            "/Users/me/|/Users/me/Library/Application Support|/Users/me/Library/Application Support|/Users/me/Library/Caches"
+        );
+
+        let windows = dirs_cmd(
+            Platform::Windows,
+            &[
+                ("USERPROFILE", r"C:\Users\me"),
+                ("APPDATA", r"C:\Users\me\AppData\Roaming"),
+                ("LOCALAPPDATA", r"C:\Users\me\AppData\Local"),
+            ],
+        );
+        assert_eq!(
+            interpolate(windows, template).await,
+            r"C:\Users\me|C:\Users\me\AppData\Roaming|C:\Users\me\AppData\Roaming|C:\Users\me\AppData\Local"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_resolves_to_the_default_when_unavailable() {
+        let dirs_cmd = dirs_cmd(Platform::Windows, &[("USERPROFILE", "relative")]);
+        assert_eq!(
+            interpolate(
+                dirs_cmd,
+                "[${home}] [${config-dir:-none}] [${dirs:music:-?}]"
+            )
+            .await,
+            "[] [none] [?]"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_resolves_the_current_home() {
+        let mut commands: HashMap<&str, Arc<tokio::sync::Mutex<dyn JakartaCommand>>> =
+            HashMap::new();
+        commands.insert(
+            "home",
+            Arc::new(tokio::sync::Mutex::new(DirsCommand::new())),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta.interpolate_string("${home}".to_owned()).await;
+        if let Some(home) = std::env::var("HOME")
+            .ok()
+            .filter(|home| home.starts_with('/'))
+        {
+            assert_eq!(result, home);
+        }
+    }
+}
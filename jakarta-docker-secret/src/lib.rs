@@ -0,0 +1,122 @@
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+
+const DEFAULT_SECRETS_DIR: &str = "/run/secrets";
+
+/// Resolves `${docker-secret:db_password}` to the contents of `/run/secrets/db_password`, where
+/// Docker Swarm and Compose mount secrets. The directory can be changed with `with_secrets_dir`.
+pub struct DockerSecretCommand {
+    secrets_dir: PathBuf,
+}
+
+impl DockerSecretCommand {
+    pub fn new() -> Self {
+        Self {
+            secrets_dir: PathBuf::from(DEFAULT_SECRETS_DIR),
+        }
+    }
+
+    pub fn with_secrets_dir(mut self, secrets_dir: impl Into<PathBuf>) -> Self {
+        self.secrets_dir = secrets_dir.into();
+        self
+    }
+
+    /// Only allows plain secret names, so placeholders can't read files outside the secrets dir.
+    fn resolve(&self, name: &str) -> Option<PathBuf> {
+        let mut components = Path::new(name).components();
+
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(name)), None) => Some(self.secrets_dir.join(name)),
+            _ => None,
+        }
+    }
+}
+
+impl Default for DockerSecretCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for DockerSecretCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let name = args.trim();
+
+        let Some(path) = self.resolve(name) else {
+            return Err(CommandError(format!("Invalid Docker secret name {name:?}")));
+        };
+
+        std::fs::read_to_string(&path)
+            .map(jakarta::trim_trailing_newline)
+            .map_err(|err| CommandError(format!("Could not read Docker secret {name:?}: {err}")))
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn is_secret(&self) -> bool {
+        true
+    }
+
+    fn identifiers(&self) -> Vec<&'static str> {
+        vec!["docker-secret"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_reads_mounted_secrets() {
+        let secrets_dir =
+            std::env::temp_dir().join("jakarta-docker-secret-it-reads-mounted-secrets");
+        let _ = std::fs::remove_dir_all(&secrets_dir);
+        std::fs::create_dir_all(&secrets_dir).unwrap();
+        std::fs::write(secrets_dir.join("db_password"), "hunter2\n").unwrap();
+        std::fs::write(secrets_dir.join("api_key"), "${env:KEY}").unwrap();
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let secret_cmd = DockerSecretCommand::new().with_secrets_dir(&secrets_dir);
+        commands.insert("docker-secret", Arc::new(Mutex::new(secret_cmd)));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${docker-secret:db_password} ${docker-secret:api_key} [${docker-secret:missing:-none}] [${docker-secret:../db_password:-denied}]"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "hunter2 ${env:KEY} [none] [denied]".to_owned());
+
+        std::fs::remove_dir_all(&secrets_dir).unwrap();
+    }
+
+    #[test]
+    fn it_defaults_to_the_docker_mount() {
+        assert_eq!(
+            DockerSecretCommand::default().resolve("db_password"),
+            Some(PathBuf::from("/run/secrets/db_password"))
+        );
+        assert_eq!(DockerSecretCommand::default().resolve("/etc/passwd"), None);
+    }
+}
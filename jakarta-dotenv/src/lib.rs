@@ -0,0 +1,190 @@
+mod profile;
+
+use std::{collections::HashMap, path::Path};
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+
+pub use crate::profile::ProfileCommand;
+
+pub struct DotenvCommand {
+    values: HashMap<String, String>,
+}
+
+impl DotenvCommand {
+    pub fn from_path(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::from_contents(&std::fs::read_to_string(path)?))
+    }
+
+    pub fn from_contents(contents: &str) -> Self {
+        Self {
+            values: parse(contents),
+        }
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for DotenvCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        self.values
+            .get(&args)
+            .cloned()
+            .ok_or_else(|| CommandError(format!("Could not find {args} in dotenv file")))
+    }
+}
+
+pub fn parse(contents: &str) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let mut lines = contents.lines();
+
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim_start()),
+            None => continue,
+        };
+
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let mut value = value[1..].to_owned();
+
+                while !has_closing_quote(&value, quote) {
+                    match lines.next() {
+                        Some(line) => {
+                            value.push('\n');
+                            value.push_str(line);
+                        }
+                        None => break,
+                    }
+                }
+
+                unquote(&value, quote)
+            }
+            _ => value
+                .split_once(" #")
+                .map_or(value, |(value, _)| value)
+                .trim_end()
+                .to_owned(),
+        };
+
+        values.insert(key.to_owned(), value);
+    }
+
+    values
+}
+
+fn has_closing_quote(value: &str, quote: char) -> bool {
+    let mut escaped = false;
+
+    value.chars().any(|c| {
+        let closing = c == quote && !(escaped && quote == '"');
+        escaped = c == '\\' && !escaped;
+        closing
+    })
+}
+
+fn unquote(value: &str, quote: char) -> String {
+    let mut unquoted = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c == quote => break,
+            '\\' if quote == '"' => match chars.next() {
+                Some('n') => unquoted.push('\n'),
+                Some('r') => unquoted.push('\r'),
+                Some('t') => unquoted.push('\t'),
+                Some(c) => unquoted.push(c),
+                None => unquoted.push('\\'),
+            },
+            c => unquoted.push(c),
+        }
+    }
+
+    unquoted
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::sync::Arc;
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    const DOTENV: &str = r#"
+# database settings
+DATABASE_URL=postgres://localhost:5432/app # trailing comment
+export API_KEY=abc123
+DOUBLE="hello \"world\"\nnext"
+SINGLE='literal \n # not a comment'
+MULTILINE="first
+second"
+EMPTY=
+"#;
+
+    #[test]
+    fn it_parses_dotenv_files() {
+        let values = parse(DOTENV);
+
+        assert_eq!(values["DATABASE_URL"], "postgres://localhost:5432/app");
+        assert_eq!(values["API_KEY"], "abc123");
+        assert_eq!(values["DOUBLE"], "hello \"world\"\nnext");
+        assert_eq!(values["SINGLE"], r"literal \n # not a comment");
+        assert_eq!(values["MULTILINE"], "first\nsecond");
+        assert_eq!(values["EMPTY"], "");
+        assert_eq!(values.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_dotenv_values() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let path = std::env::temp_dir().join("jakarta-dotenv-it-interpolates-dotenv-values.env");
+        std::fs::write(&path, DOTENV).unwrap();
+
+        let dotenv_cmd = Arc::new(Mutex::new(DotenvCommand::from_path(&path).unwrap()));
+        commands.insert("dotenv", dotenv_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("url=${dotenv:DATABASE_URL} key=${dotenv:API_KEY}".to_owned())
+            .await;
+
+        assert_eq!(
+            result,
+            "url=postgres://localhost:5432/app key=abc123".to_owned()
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_for_missing_keys() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let dotenv_cmd = Arc::new(Mutex::new(DotenvCommand::from_contents(DOTENV)));
+        commands.insert("dotenv", dotenv_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${dotenv:MISSING_KEY:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd default_value".to_owned());
+    }
+}
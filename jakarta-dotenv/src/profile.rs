@@ -0,0 +1,112 @@
+use std::{collections::HashMap, io::ErrorKind, path::Path};
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+
+use crate::parse;
+
+/// Resolves `${profile:DATABASE_URL}` from a stack of dotenv files, loaded and merged once, where
+/// later files override earlier ones. Files that don't exist are skipped.
+pub struct ProfileCommand {
+    values: HashMap<String, String>,
+}
+
+impl ProfileCommand {
+    pub fn from_paths<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> std::io::Result<Self> {
+        let mut values = HashMap::new();
+
+        for path in paths {
+            match std::fs::read_to_string(path) {
+                Ok(contents) => values.extend(parse(&contents)),
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(Self { values })
+    }
+
+    /// Loads `.env`, `.env.local`, `.env.<profile>` and `.env.<profile>.local` from `dir`, in
+    /// that order of precedence.
+    pub fn for_profile(dir: impl AsRef<Path>, profile: &str) -> std::io::Result<Self> {
+        let dir = dir.as_ref();
+
+        Self::from_paths([
+            dir.join(".env"),
+            dir.join(".env.local"),
+            dir.join(format!(".env.{profile}")),
+            dir.join(format!(".env.{profile}.local")),
+        ])
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for ProfileCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        self.values
+            .get(&args)
+            .cloned()
+            .ok_or_else(|| CommandError(format!("Could not find {args} in any profile file")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::sync::Arc;
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_overrides_values_by_profile() {
+        let dir = std::env::temp_dir().join("jakarta-dotenv-it-overrides-values-by-profile");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        std::fs::write(
+            dir.join(".env"),
+            "DATABASE_URL=postgres://localhost/app\nLOG_LEVEL=debug\nPORT=8080\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join(".env.local"), "LOG_LEVEL=trace\n").unwrap();
+        std::fs::write(
+            dir.join(".env.production"),
+            "DATABASE_URL=postgres://db.internal/app\nLOG_LEVEL=warn\n",
+        )
+        .unwrap();
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let profile_cmd = ProfileCommand::for_profile(&dir, "production").unwrap();
+        commands.insert("profile", Arc::new(Mutex::new(profile_cmd)));
+        let development_cmd =
+            ProfileCommand::from_paths([dir.join(".env"), dir.join(".env.local")]).unwrap();
+        commands.insert("dev", Arc::new(Mutex::new(development_cmd)));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${profile:DATABASE_URL} ${profile:LOG_LEVEL} ${profile:PORT} ${profile:MISSING:-fallback} | ${dev:DATABASE_URL} ${dev:LOG_LEVEL}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            "postgres://db.internal/app warn 8080 fallback | postgres://localhost/app trace"
+                .to_owned()
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
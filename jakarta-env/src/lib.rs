@@ -1,21 +1,93 @@
 use async_trait::async_trait;
+use jakarta::{CommandError, Context};
 
-pub struct EnvCommand {}
+/// How a configured prefix is applied to names that already start with it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PrefixStrategy {
+    /// `${env:MYAPP_URL}` looks up `MYAPP_URL` rather than `MYAPP_MYAPP_URL`.
+    #[default]
+    SkipPrefixed,
+    /// Every name is prefixed, even if it already starts with the prefix.
+    Always,
+}
+
+#[derive(Default)]
+pub struct EnvCommand {
+    prefix: Option<String>,
+    prefix_strategy: PrefixStrategy,
+}
+
+impl EnvCommand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Looks up `${env:DATABASE_URL}` as `{prefix}DATABASE_URL`, e.g. `MYAPP_DATABASE_URL`.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn with_prefix_strategy(mut self, prefix_strategy: PrefixStrategy) -> Self {
+        self.prefix_strategy = prefix_strategy;
+        self
+    }
+
+    fn variable_name(&self, name: String) -> String {
+        match &self.prefix {
+            Some(prefix)
+                if self.prefix_strategy == PrefixStrategy::SkipPrefixed
+                    && name.starts_with(prefix.as_str()) =>
+            {
+                name
+            }
+            Some(prefix) => format!("{prefix}{name}"),
+            None => name,
+        }
+    }
+}
+
+fn select_field(value: String, field: &str) -> Option<String> {
+    match field {
+        "trim" => Some(value.trim().to_owned()),
+        "raw" => Some(value),
+        field => field
+            .parse::<usize>()
+            .ok()
+            .and_then(|idx| std::env::split_paths(&value).nth(idx))
+            .map(|path| path.display().to_string()),
+    }
+}
 
 #[async_trait]
 impl jakarta::JakartaCommand for EnvCommand {
-    async fn process(&mut self, _: String, args: String, default_value: Option<String>) -> String {
-        std::env::var(args.clone()).unwrap_or_else(|_| {
-            tracing::warn!("Could not get environment variable {args}, resolving to default value");
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let args = self.variable_name(args);
+        let value = std::env::var(args.clone())
+            .map_err(|_| CommandError(format!("Could not get environment variable {args}")))?;
 
-            default_value.unwrap_or_else(|| "".to_owned())
-        })
+        match field {
+            Some(field) => select_field(value, &field).ok_or_else(|| {
+                CommandError(format!(
+                    "Could not select {field:?} from environment variable {args}"
+                ))
+            }),
+            None => Ok(jakarta::trim_trailing_newline(value)),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use jakarta::{Jakarta, JakartaCommand};
+    use jakarta::{Jakarta, JakartaCommand, Warning, WarningReason};
     use std::{collections::HashMap, sync::Arc};
 
     use tokio::sync::Mutex;
@@ -26,7 +98,7 @@ mod tests {
     async fn it_interpolates_env_variables() {
         let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
 
-        let env_cmd = Arc::new(Mutex::new(EnvCommand {}));
+        let env_cmd = Arc::new(Mutex::new(EnvCommand::new()));
         commands.insert("env", env_cmd.clone());
         let jakarta = Jakarta::new(commands).unwrap();
 
@@ -54,7 +126,7 @@ mod tests {
     async fn it_interpolates_constructed_env_vars() {
         let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
 
-        let env_cmd = Arc::new(Mutex::new(EnvCommand {}));
+        let env_cmd = Arc::new(Mutex::new(EnvCommand::new()));
         commands.insert("env", env_cmd.clone());
         let jakarta = Jakarta::new(commands).unwrap();
 
@@ -71,7 +143,7 @@ mod tests {
     async fn it_interpolates_constructed_env_vars_from_default_value() {
         let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
 
-        let env_cmd = Arc::new(Mutex::new(EnvCommand {}));
+        let env_cmd = Arc::new(Mutex::new(EnvCommand::new()));
         commands.insert("env", env_cmd.clone());
         let jakarta = Jakarta::new(commands).unwrap();
 
@@ -82,4 +154,133 @@ mod tests {
 
         assert_eq!(result, "asd VAR_VALUE".to_owned());
     }
+
+    #[tokio::test]
+    async fn it_selects_fields_from_env_variables() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let env_cmd = Arc::new(Mutex::new(EnvCommand::new()));
+        commands.insert("env", env_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let paths = std::env::join_paths(["/usr/local/bin", "/usr/bin"]).unwrap();
+        std::env::set_var("FIELD_PATH", paths);
+        std::env::set_var("FIELD_PADDED", "  padded  ");
+
+        let result = jakarta
+            .interpolate_string(
+                "${env:FIELD_PATH#0} ${env:FIELD_PATH#1} [${env:FIELD_PADDED#trim}]".to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "/usr/local/bin /usr/bin [padded]".to_owned());
+
+        let result = jakarta
+            .interpolate_string("${env:FIELD_PATH#2:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "default_value".to_owned());
+
+        let first_path = std::env::split_paths(&std::env::var("PATH").unwrap()).next();
+        let result = jakarta.interpolate_string("${env:PATH#0}".to_owned()).await;
+
+        assert_eq!(
+            Some(result),
+            first_path.map(|path| path.display().to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn it_trims_trailing_newlines_unless_raw() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let env_cmd = Arc::new(Mutex::new(EnvCommand::new()));
+        commands.insert("env", env_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        std::env::set_var("VAR_NEWLINE", "https://example.com\n");
+        let result = jakarta
+            .interpolate_string("[${env:VAR_NEWLINE}] [${env:VAR_NEWLINE#raw}]".to_owned())
+            .await;
+
+        assert_eq!(
+            result,
+            "[https://example.com] [https://example.com\n]".to_owned()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_looks_up_prefixed_env_variables() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert(
+            "env",
+            Arc::new(Mutex::new(EnvCommand::new().with_prefix("MYAPP_"))),
+        );
+        commands.insert(
+            "always",
+            Arc::new(Mutex::new(
+                EnvCommand::new()
+                    .with_prefix("MYAPP_")
+                    .with_prefix_strategy(PrefixStrategy::Always),
+            )),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        std::env::set_var("MYAPP_DATABASE_URL", "postgres://db");
+        std::env::set_var("MYAPP_MYAPP_NESTED", "double");
+        std::env::set_var("DATABASE_URL", "unprefixed");
+        let result = jakarta
+            .interpolate_string(
+                "${env:DATABASE_URL} ${env:MYAPP_DATABASE_URL} ${always:MYAPP_NESTED} ${always:MYAPP_DATABASE_URL:-missing}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            "postgres://db postgres://db double missing".to_owned()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_distinguishes_empty_from_unset_variables() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("env", Arc::new(Mutex::new(EnvCommand::new())));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        std::env::set_var("EMPTY_VAR", "");
+        std::env::remove_var("UNSET_VAR");
+        let result = jakarta
+            .interpolate_string(
+                "[${env:EMPTY_VAR:-default}] [${env:EMPTY_VAR:=default}] [${env:UNSET_VAR:-default}] [${env:UNSET_VAR:=default}]"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "[default] [] [default] [default]".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_warns_about_missing_variables() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("env", Arc::new(Mutex::new(EnvCommand::new())));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        std::env::remove_var("MISSING_WARNING_VAR");
+        let (result, warnings) = jakarta
+            .interpolate_collecting("[${env:MISSING_WARNING_VAR}]".to_owned())
+            .await;
+
+        assert_eq!(result, "[]".to_owned());
+        assert_eq!(
+            warnings,
+            vec![Warning {
+                placeholder: "${env:MISSING_WARNING_VAR}".to_owned(),
+                reason: WarningReason::Reported(
+                    "Could not get environment variable MISSING_WARNING_VAR, resolving to default value"
+                        .to_owned()
+                ),
+            }]
+        );
+    }
 }
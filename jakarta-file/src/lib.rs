@@ -0,0 +1,176 @@
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+
+pub struct FileCommand {}
+
+#[async_trait]
+impl jakarta::JakartaCommand for FileCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let path = args.as_str();
+
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| CommandError(format!("Could not read file {path:?}: {err}")))?;
+
+        let selector = match field.as_deref() {
+            Some("raw") => return Ok(contents),
+            Some(field) => jakarta::FieldSelector::parse(field),
+            None => None,
+        };
+
+        match selector {
+            Some(selector) => selector.select(&contents).ok_or_else(|| {
+                CommandError(format!(
+                    "Could not select {:?} from file {path:?}",
+                    field.unwrap_or_default()
+                ))
+            }),
+            None => Ok(jakarta::trim_trailing_newline(contents)),
+        }
+    }
+
+    async fn process_multi(
+        &mut self,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        default_value: Option<String>,
+    ) -> Vec<String> {
+        let path = args.as_str();
+
+        match std::fs::read_to_string(path) {
+            Ok(contents) => contents.lines().map(str::to_owned).collect(),
+            Err(err) => {
+                tracing::warn!("Could not read file {path:?}: {err}, resolving to default value");
+
+                default_value.into_iter().collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_interpolates_file_contents() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let file_cmd = Arc::new(Mutex::new(FileCommand {}));
+        commands.insert("file", file_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let path = std::env::temp_dir().join("jakarta-file-it-interpolates-file-contents.pem");
+        std::fs::write(&path, "-----BEGIN CERTIFICATE-----\n").unwrap();
+
+        let result = jakarta
+            .interpolate_string(format!("cert: ${{file:{}}}", path.display()))
+            .await;
+
+        assert_eq!(result, "cert: -----BEGIN CERTIFICATE-----".to_owned());
+
+        let result = jakarta
+            .interpolate_string(format!("cert: ${{file:{}#raw}}", path.display()))
+            .await;
+
+        assert_eq!(result, "cert: -----BEGIN CERTIFICATE-----\n".to_owned());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_for_missing_files() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let file_cmd = Arc::new(Mutex::new(FileCommand {}));
+        commands.insert("file", file_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${file:/nonexistent/jakarta/file}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd ".to_owned());
+
+        let result = jakarta
+            .interpolate_string("asd ${file:/nonexistent/jakarta/file:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd default_value".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_joins_file_lines() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let file_cmd = Arc::new(Mutex::new(FileCommand {}));
+        commands.insert("file", file_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let path = std::env::temp_dir().join("jakarta-file-it-joins-file-lines.txt");
+        std::fs::write(&path, "a.example.com\nb.example.com\n").unwrap();
+
+        let result = jakarta
+            .interpolate_string(format!("hosts: ${{file:{}[*]:, }}", path.display()))
+            .await;
+
+        assert_eq!(result, "hosts: a.example.com, b.example.com".to_owned());
+
+        let result = jakarta
+            .interpolate_string(format!("hosts:\n${{file:{}[*]:\n}}", path.display()))
+            .await;
+
+        assert_eq!(result, "hosts:\na.example.com\nb.example.com".to_owned());
+
+        let result = jakarta
+            .interpolate_string("hosts: ${file:/nonexistent/jakarta/file[*]:-none}".to_owned())
+            .await;
+
+        assert_eq!(result, "hosts: none".to_owned());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_selects_lines_and_keys() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let file_cmd = Arc::new(Mutex::new(FileCommand {}));
+        commands.insert("file", file_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let path = std::env::temp_dir().join("jakarta-file-it-selects-lines-and-keys.env");
+        std::fs::write(&path, "HOST=db.internal\nPORT=5432\n").unwrap();
+        let path = path.display();
+
+        let result = jakarta
+            .interpolate_string(format!("${{file:{path}#line:2}} ${{file:{path}#kv:HOST}}"))
+            .await;
+
+        assert_eq!(result, "PORT=5432 db.internal".to_owned());
+
+        let result = jakarta
+            .interpolate_string(format!(
+                "${{file:{path}#line:3:-no_line}} ${{file:{path}#kv:USER:-no_key}}"
+            ))
+            .await;
+
+        assert_eq!(result, "no_line no_key".to_owned());
+
+        std::fs::remove_file(path.to_string()).unwrap();
+    }
+}
@@ -0,0 +1,178 @@
+#[cfg(feature = "sdk")]
+mod sdk;
+
+#[cfg(feature = "sdk")]
+pub use crate::sdk::SdkClient;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum GcpSecretError {
+    #[error("secret version {0:?} not found")]
+    NotFound(String),
+    #[error("permission denied on secret version {0:?}")]
+    PermissionDenied(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Accesses the payload of a secret version; [`SdkClient`] calls the Secret Manager API. `name`
+/// is always a full `projects/<project>/secrets/<secret>/versions/<version>` resource name.
+#[async_trait]
+pub trait SecretManagerClient {
+    async fn access_secret_version(&self, name: &str) -> Result<Vec<u8>, GcpSecretError>;
+}
+
+fn secret_version_name(path: &str) -> Option<String> {
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+
+    match segments.as_slice() {
+        ["projects", project, "secrets", secret] if !project.is_empty() && !secret.is_empty() => {
+            Some(format!(
+                "projects/{project}/secrets/{secret}/versions/latest"
+            ))
+        }
+        ["projects", project, "secrets", secret, "versions", version]
+            if !project.is_empty() && !secret.is_empty() && !version.is_empty() =>
+        {
+            Some(format!(
+                "projects/{project}/secrets/{secret}/versions/{version}"
+            ))
+        }
+        _ => None,
+    }
+}
+
+pub struct GcpSecretCommand {
+    client: Box<dyn SecretManagerClient + Send + Sync>,
+}
+
+impl GcpSecretCommand {
+    pub fn new(client: impl SecretManagerClient + Send + Sync + 'static) -> Self {
+        Self {
+            client: Box::new(client),
+        }
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for GcpSecretCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let name = secret_version_name(&args).ok_or_else(|| {
+            CommandError(format!(
+                "Invalid secret path {args:?}, expected projects/<project>/secrets/<secret>[/versions/<version>]"
+            ))
+        })?;
+
+        let payload = self
+            .client
+            .access_secret_version(&name)
+            .await
+            .map_err(|err| CommandError(format!("Could not access secret {name:?}: {err}")))?;
+
+        String::from_utf8(payload)
+            .map_err(|err| CommandError(format!("Secret {name:?} is not valid UTF-8: {err}")))
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn is_secret(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    struct StubClient {}
+
+    #[async_trait]
+    impl SecretManagerClient for StubClient {
+        async fn access_secret_version(&self, name: &str) -> Result<Vec<u8>, GcpSecretError> {
+            match name {
+                "projects/123/secrets/db-pass/versions/latest" => Ok(b"hunter2".to_vec()),
+                "projects/123/secrets/db-pass/versions/1" => Ok(b"hunter1".to_vec()),
+                "projects/123/secrets/binary/versions/latest" => Ok(vec![0xff, 0xfe]),
+                "projects/123/secrets/restricted/versions/latest" => {
+                    Err(GcpSecretError::PermissionDenied(name.to_owned()))
+                }
+                _ => Err(GcpSecretError::NotFound(name.to_owned())),
+            }
+        }
+    }
+
+    #[test]
+    fn it_parses_secret_paths() {
+        assert_eq!(
+            secret_version_name("projects/123/secrets/db-pass"),
+            Some("projects/123/secrets/db-pass/versions/latest".to_owned())
+        );
+        assert_eq!(
+            secret_version_name("/projects/123/secrets/db-pass/versions/4/"),
+            Some("projects/123/secrets/db-pass/versions/4".to_owned())
+        );
+        assert_eq!(secret_version_name("db-pass"), None);
+        assert_eq!(secret_version_name("projects/123/secrets/"), None);
+        assert_eq!(
+            secret_version_name("projects/123/secrets/db-pass/versions"),
+            None
+        );
+        assert_eq!(secret_version_name("projects/123/keys/db-pass"), None);
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_secrets() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let gcp_cmd = Arc::new(Mutex::new(GcpSecretCommand::new(StubClient {})));
+        commands.insert("gcp-sm", gcp_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${gcp-sm:projects/123/secrets/db-pass} ${gcp-sm:projects/123/secrets/db-pass/versions/1}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "hunter2 hunter1".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_on_errors() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let gcp_cmd = Arc::new(Mutex::new(GcpSecretCommand::new(StubClient {})));
+        commands.insert("gcp-sm", gcp_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        for template in [
+            "${gcp-sm:projects/123/secrets/missing:-default_value}",
+            "${gcp-sm:projects/123/secrets/restricted:-default_value}",
+            "${gcp-sm:projects/123/secrets/binary:-default_value}",
+            "${gcp-sm:db-pass:-default_value}",
+        ] {
+            let result = jakarta.interpolate_string(template.to_owned()).await;
+
+            assert_eq!(result, "default_value".to_owned());
+        }
+    }
+}
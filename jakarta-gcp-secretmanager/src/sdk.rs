@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use google_cloud_secretmanager_v1::client::SecretManagerService;
+
+use crate::{GcpSecretError, SecretManagerClient};
+
+/// The default [`SecretManagerClient`], backed by google-cloud-secretmanager-v1.
+#[derive(Clone, Debug)]
+pub struct SdkClient {
+    client: SecretManagerService,
+}
+
+impl SdkClient {
+    /// Authenticates with Application Default Credentials, i.e. `GOOGLE_APPLICATION_CREDENTIALS`,
+    /// the gcloud CLI login or the metadata server.
+    pub async fn from_env() -> Result<Self, GcpSecretError> {
+        let client = SecretManagerService::builder()
+            .build()
+            .await
+            .map_err(|err| GcpSecretError::Other(err.to_string()))?;
+
+        Ok(Self::from_client(client))
+    }
+
+    pub fn from_client(client: SecretManagerService) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl SecretManagerClient for SdkClient {
+    async fn access_secret_version(&self, name: &str) -> Result<Vec<u8>, GcpSecretError> {
+        let response = self
+            .client
+            .access_secret_version()
+            .set_name(name)
+            .send()
+            .await
+            .map_err(|err| match err.http_status_code() {
+                Some(404) => GcpSecretError::NotFound(name.to_owned()),
+                Some(403) => GcpSecretError::PermissionDenied(name.to_owned()),
+                _ => GcpSecretError::Other(err.to_string()),
+            })?;
+
+        Ok(response
+            .payload
+            .map(|payload| payload.data.to_vec())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use google_cloud_auth::credentials::anonymous;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    async fn client(server: &MockServer) -> SdkClient {
+        let client = SecretManagerService::builder()
+            .with_endpoint(server.uri())
+            .with_credentials(anonymous::Builder::new().build())
+            .build()
+            .await
+            .unwrap();
+
+        SdkClient::from_client(client)
+    }
+
+    fn error(code: u16, status: &str) -> ResponseTemplate {
+        ResponseTemplate::new(code).set_body_json(serde_json::json!({
+            "error": { "code": code, "message": status, "status": status }
+        }))
+    }
+
+    #[tokio::test]
+    async fn it_accesses_secret_versions() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/projects/p/secrets/db/versions/latest:access"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "name": "projects/p/secrets/db/versions/3",
+                "payload": { "data": "aHVudGVyMg==" }
+            })))
+            .mount(&server)
+            .await;
+
+        let payload = client(&server)
+            .await
+            .access_secret_version("projects/p/secrets/db/versions/latest")
+            .await
+            .unwrap();
+
+        assert_eq!(payload, b"hunter2".to_vec());
+    }
+
+    #[tokio::test]
+    async fn it_maps_status_codes() {
+        let server = MockServer::start().await;
+        Mock::given(path("/v1/projects/p/secrets/missing/versions/1:access"))
+            .respond_with(error(404, "NOT_FOUND"))
+            .mount(&server)
+            .await;
+        Mock::given(path("/v1/projects/p/secrets/restricted/versions/1:access"))
+            .respond_with(error(403, "PERMISSION_DENIED"))
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+
+        assert!(matches!(
+            client
+                .access_secret_version("projects/p/secrets/missing/versions/1")
+                .await,
+            Err(GcpSecretError::NotFound(_))
+        ));
+        assert!(matches!(
+            client
+                .access_secret_version("projects/p/secrets/restricted/versions/1")
+                .await,
+            Err(GcpSecretError::PermissionDenied(_))
+        ));
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+
+/// Resolves `${git:sha}`, `${git:short_sha}`, `${git:branch}`, `${git:tag}` and
+/// `${git:describe}` from the repository in the working directory, or the one configured with
+/// `with_repo_dir`.
+#[derive(Default)]
+pub struct GitCommand {
+    repo_dir: Option<PathBuf>,
+}
+
+impl GitCommand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_repo_dir(mut self, repo_dir: impl Into<PathBuf>) -> Self {
+        self.repo_dir = Some(repo_dir.into());
+        self
+    }
+
+    async fn git(&self, args: &[&str]) -> Result<String, String> {
+        let mut cmd = tokio::process::Command::new("git");
+        cmd.args(args);
+        if let Some(repo_dir) = &self.repo_dir {
+            cmd.current_dir(repo_dir);
+        }
+
+        let output = cmd.output().await.map_err(|err| err.to_string())?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr)
+                .trim_end()
+                .to_owned());
+        }
+
+        String::from_utf8(output.stdout)
+            .map(jakarta::trim_trailing_newline)
+            .map_err(|err| err.to_string())
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for GitCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let git_args: &[&str] = match args.trim() {
+            "sha" => &["rev-parse", "HEAD"],
+            "short_sha" => &["rev-parse", "--short", "HEAD"],
+            "branch" => &["symbolic-ref", "--short", "HEAD"],
+            "tag" => &["describe", "--tags", "--exact-match", "HEAD"],
+            "describe" => &["describe", "--tags", "--always", "--dirty"],
+            value => return Err(CommandError(format!("Unknown git value {value:?}"))),
+        };
+
+        self.git(git_args)
+            .await
+            .map_err(|err| CommandError(format!("Could not get git {args}: {err}")))
+    }
+
+    fn cacheable(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, path::Path, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    fn git(repo_dir: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args([
+                "-c",
+                "user.name=Jakarta",
+                "-c",
+                "user.email=jakarta@example.com",
+            ])
+            .args(args)
+            .current_dir(repo_dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+
+        String::from_utf8(output.stdout).unwrap().trim().to_owned()
+    }
+
+    #[tokio::test]
+    async fn it_resolves_repository_values() {
+        let repo_dir = std::env::temp_dir().join("jakarta-git-it-resolves-repository-values");
+        let _ = std::fs::remove_dir_all(&repo_dir);
+        std::fs::create_dir_all(&repo_dir).unwrap();
+
+        git(&repo_dir, &["init", "-q", "-b", "release"]);
+        git(
+            &repo_dir,
+            &["commit", "-q", "--allow-empty", "-m", "initial"],
+        );
+        git(&repo_dir, &["tag", "v1.2.3"]);
+        let sha = git(&repo_dir, &["rev-parse", "HEAD"]);
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert(
+            "git",
+            Arc::new(Mutex::new(GitCommand::new().with_repo_dir(&repo_dir))),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${git:sha} ${git:short_sha} ${git:branch} ${git:tag} ${git:describe} ${git:author:-?}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            format!("{sha} {} release v1.2.3 v1.2.3 ?", &sha[..7])
+        );
+
+        git(&repo_dir, &["commit", "-q", "--allow-empty", "-m", "next"]);
+        let result = jakarta
+            .interpolate_string("${git:tag:-untagged} ${git:describe}".to_owned())
+            .await;
+
+        assert!(result.starts_with("untagged v1.2.3-1-g"), "{result}");
+
+        std::fs::remove_dir_all(&repo_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_resolves_to_the_default_outside_of_repositories() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert(
+            "git",
+            Arc::new(Mutex::new(GitCommand::new().with_repo_dir(Path::new("/")))),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${git:sha:-unknown}".to_owned())
+            .await;
+
+        assert_eq!(result, "unknown".to_owned());
+    }
+}
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+
+mod digest;
+
+pub use digest::{md5, sha1, sha256};
+
+/// Hex digest of the `#field` payload, e.g. `${hash:sha256#${file:build.js}}`.
+pub struct HashCommand {}
+
+#[async_trait]
+impl jakarta::JakartaCommand for HashCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let payload = field.unwrap_or_default();
+
+        match args.trim() {
+            "sha256" => Ok(sha256(payload.as_bytes())),
+            "sha1" => Ok(sha1(payload.as_bytes())),
+            "md5" => Ok(md5(payload.as_bytes())),
+            algorithm => Err(CommandError(format!(
+                "Unknown hash algorithm {algorithm:?}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_hashes_values() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let hash_cmd = Arc::new(Mutex::new(HashCommand {}));
+        commands.insert("hash", hash_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let input = "The quick brown fox jumps over the lazy dog";
+
+        let result = jakarta
+            .interpolate_string(format!("${{hash:sha256#{input}}}"))
+            .await;
+
+        assert_eq!(
+            result,
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592".to_owned()
+        );
+
+        let result = jakarta
+            .interpolate_string(format!("${{hash:sha1#{input}}}"))
+            .await;
+
+        assert_eq!(
+            result,
+            "2fd4e1c67a2d28fced849ee1bb76e7391b93eb12".to_owned()
+        );
+
+        let result = jakarta
+            .interpolate_string(format!("${{hash:md5#{input}}}"))
+            .await;
+
+        assert_eq!(result, "9e107d9d372bb6826bd81d3542a419d6".to_owned());
+
+        let result = jakarta.interpolate_string("${hash:md5}".to_owned()).await;
+
+        assert_eq!(result, "d41d8cd98f00b204e9800998ecf8427e".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_for_unknown_algorithms() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let hash_cmd = Arc::new(Mutex::new(HashCommand {}));
+        commands.insert("hash", hash_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${hash:crc32#abc:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "default_value".to_owned());
+    }
+}
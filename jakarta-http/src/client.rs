@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum HttpError {
+    #[error("request timed out")]
+    Timeout,
+    #[error("malformed response")]
+    MalformedResponse,
+    #[error("response body is not valid UTF-8")]
+    InvalidBody,
+    #[error("request failed: {0}")]
+    Request(#[from] reqwest::Error),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+#[async_trait]
+pub trait HttpClient {
+    async fn get(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        timeout: Duration,
+    ) -> Result<HttpResponse, HttpError>;
+}
+
+/// The default [`HttpClient`], backed by reqwest with rustls. Follows up to 10 redirects.
+#[derive(Clone, Default)]
+pub struct ReqwestClient {
+    client: reqwest::Client,
+}
+
+impl ReqwestClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends requests through a preconfigured client, e.g. one with custom root certificates
+    /// or a proxy.
+    pub fn from_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl HttpClient for ReqwestClient {
+    async fn get(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        timeout: Duration,
+    ) -> Result<HttpResponse, HttpError> {
+        let request = headers.iter().fold(
+            self.client.get(url).timeout(timeout),
+            |request, (name, value)| request.header(name, value),
+        );
+
+        let response = request.send().await.map_err(map_error)?;
+        let status = response.status().as_u16();
+        let body = response.bytes().await.map_err(map_error)?;
+
+        Ok(HttpResponse {
+            status,
+            body: String::from_utf8(body.to_vec()).map_err(|_| HttpError::InvalidBody)?,
+        })
+    }
+}
+
+fn map_error(err: reqwest::Error) -> HttpError {
+    match err.is_timeout() {
+        true => HttpError::Timeout,
+        false => HttpError::Request(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_sends_headers_and_follows_redirects() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/old"))
+            .respond_with(ResponseTemplate::new(302).insert_header("Location", "/new"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/new"))
+            .and(header("Authorization", "Bearer token"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+            .mount(&server)
+            .await;
+
+        let response = ReqwestClient::new()
+            .get(
+                &format!("{}/old", server.uri()),
+                &[("Authorization".to_owned(), "Bearer token".to_owned())],
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response,
+            HttpResponse {
+                status: 200,
+                body: "hello".to_owned()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_non_utf8_bodies() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0xff, 0xfe]))
+            .mount(&server)
+            .await;
+
+        let result = ReqwestClient::new()
+            .get(&server.uri(), &[], Duration::from_secs(5))
+            .await;
+
+        assert!(matches!(result, Err(HttpError::InvalidBody)));
+    }
+
+    #[tokio::test]
+    async fn it_times_out() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&server)
+            .await;
+
+        let result = ReqwestClient::new()
+            .get(&server.uri(), &[], Duration::from_millis(100))
+            .await;
+
+        assert!(matches!(result, Err(HttpError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn it_speaks_tls_for_https_urls() {
+        let server = MockServer::start().await;
+        let url = server.uri().replacen("http://", "https://", 1);
+
+        let result = ReqwestClient::new()
+            .get(&url, &[], Duration::from_secs(5))
+            .await;
+
+        // The mock server only speaks plain HTTP, so the TLS handshake itself has to fail.
+        assert!(matches!(result, Err(HttpError::Request(err)) if err.is_connect()));
+    }
+}
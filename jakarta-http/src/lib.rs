@@ -0,0 +1,254 @@
+mod client;
+
+pub use crate::client::*;
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use jakarta_json::{Value, ValueExt};
+
+/// Fetches `${http:https://config.internal/token}` with a GET request, substituting the response
+/// body or, with `#field`, a field of the JSON response.
+pub struct HttpCommand {
+    client: Box<dyn HttpClient + Send + Sync>,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+}
+
+impl HttpCommand {
+    pub fn new() -> Self {
+        Self {
+            client: Box::new(ReqwestClient::new()),
+            headers: vec![],
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    pub fn with_client(mut self, client: impl HttpClient + Send + Sync + 'static) -> Self {
+        self.client = Box::new(client);
+        self
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl Default for HttpCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for HttpCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let url = args.as_str();
+
+        let response = match self.client.get(url, &self.headers, self.timeout).await {
+            Ok(response) if response.is_success() => response,
+            Ok(response) => {
+                return Err(CommandError(format!(
+                    "Request to {url:?} returned status {}",
+                    response.status
+                )))
+            }
+            Err(err) => return Err(CommandError(format!("Request to {url:?} failed: {err}"))),
+        };
+
+        let field = match field {
+            Some(field) if field == "raw" => return Ok(response.body),
+            Some(field) => field,
+            None => return Ok(jakarta::trim_trailing_newline(response.body)),
+        };
+
+        response
+            .body
+            .parse::<Value>()
+            .ok()
+            .and_then(|value| value.select(&field).map(Value::to_text))
+            .ok_or_else(|| {
+                CommandError(format!("Could not find {field:?} in response from {url:?}"))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    async fn mock_server(status: u16, body: &'static str) -> MockServer {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(header("Authorization", "Bearer token"))
+            .respond_with(ResponseTemplate::new(status).set_body_string(body))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("unauthorized"))
+            .mount(&server)
+            .await;
+
+        server
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_response_bodies() {
+        let server = mock_server(200, r#"{"data":{"token":"s3cr3t"}}"#).await;
+        let url = server.uri();
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let http_cmd = Arc::new(Mutex::new(
+            HttpCommand::new().with_header("Authorization", "Bearer token"),
+        ));
+        commands.insert("http", http_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(format!("${{http:{url}/token}}"))
+            .await;
+
+        assert_eq!(result, r#"{"data":{"token":"s3cr3t"}}"#.to_owned());
+
+        let result = jakarta
+            .interpolate_string(format!("${{http:{url}/token#data.token}}"))
+            .await;
+
+        assert_eq!(result, "s3cr3t".to_owned());
+
+        let result = jakarta
+            .interpolate_string(format!("${{http:{url}/token#data.missing:-default_value}}"))
+            .await;
+
+        assert_eq!(result, "default_value".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_on_error_status() {
+        let server = mock_server(503, "down").await;
+        let url = server.uri();
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let http_cmd = Arc::new(Mutex::new(
+            HttpCommand::new()
+                .with_header("Authorization", "Bearer token")
+                .with_timeout(Duration::from_secs(1)),
+        ));
+        commands.insert("http", http_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(format!(
+                "${{http:{url}/token:-default_value}} [${{http:{url}/token}}]"
+            ))
+            .await;
+
+        assert_eq!(result, "default_value []".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_follows_redirects() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/moved"))
+            .respond_with(ResponseTemplate::new(301).insert_header("Location", "/token"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("s3cr3t"))
+            .mount(&server)
+            .await;
+        let url = server.uri();
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("http", Arc::new(Mutex::new(HttpCommand::new())));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(format!("${{http:{url}/moved}}"))
+            .await;
+
+        assert_eq!(result, "s3cr3t".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_trims_trailing_newlines_unless_raw() {
+        let server = mock_server(200, "s3cr3t\n").await;
+        let url = server.uri();
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let http_cmd = Arc::new(Mutex::new(
+            HttpCommand::new().with_header("Authorization", "Bearer token"),
+        ));
+        commands.insert("http", http_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(format!(
+                "[${{http:{url}/token}}] [${{http:{url}/token#raw}}]"
+            ))
+            .await;
+
+        assert_eq!(result, "[s3cr3t] [s3cr3t\n]".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_retries_failed_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("down"))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("up"))
+            .mount(&server)
+            .await;
+        let url = server.uri();
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let http_cmd = Arc::new(Mutex::new(jakarta::RetryCommand::new(
+            HttpCommand::new(),
+            2,
+            Duration::from_millis(1),
+        )));
+        commands.insert("http", http_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(format!("${{http:{url}/health:-default_value}}"))
+            .await;
+
+        assert_eq!(result, "up".to_owned());
+    }
+}
@@ -0,0 +1,314 @@
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context, ParsedPlaceholder};
+
+const DEFAULT_MAX_DEPTH: usize = 8;
+
+/// Splices the contents of `${include:partials/header.yaml}` into the template, resolved relative
+/// to `base_dir`. Includes nested in the included files are inlined right away so cycles and the
+/// maximum depth can be enforced, all other placeholders are interpolated as usual.
+pub struct IncludeCommand {
+    base_dir: PathBuf,
+    max_depth: usize,
+}
+
+impl IncludeCommand {
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Only allows relative paths that stay within `base_dir`.
+    fn resolve(&self, path: &str) -> Result<PathBuf, CommandError> {
+        let path = Path::new(path);
+
+        if path.as_os_str().is_empty()
+            || !path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(CommandError(format!(
+                "Include {path:?} must be relative to the base directory"
+            )));
+        }
+
+        Ok(self.base_dir.join(path))
+    }
+
+    fn include(
+        &self,
+        context: &Context,
+        command: &str,
+        path: &str,
+        stack: &mut Vec<PathBuf>,
+    ) -> Result<String, CommandError> {
+        let resolved = self.resolve(path)?;
+
+        if stack.contains(&resolved) {
+            let cycle = stack
+                .iter()
+                .chain([&resolved])
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>();
+
+            return Err(CommandError(format!(
+                "Include cycle {}",
+                cycle.join(" -> ")
+            )));
+        }
+        if stack.len() >= self.max_depth {
+            return Err(CommandError(format!(
+                "Include {path:?} exceeds the maximum depth of {}",
+                self.max_depth
+            )));
+        }
+
+        let contents = std::fs::read_to_string(&resolved)
+            .map_err(|err| CommandError(format!("Could not read include {path:?}: {err}")))?;
+
+        stack.push(resolved);
+        let contents = self.inline_includes(context, command, &contents, stack);
+        stack.pop();
+
+        contents.map(jakarta::trim_trailing_newline)
+    }
+
+    /// Replaces every `${command:path}` and `${command:path:-default}` in `contents`. Includes
+    /// with a field or filters are left for regular interpolation.
+    fn inline_includes(
+        &self,
+        context: &Context,
+        command: &str,
+        contents: &str,
+        stack: &mut Vec<PathBuf>,
+    ) -> Result<String, CommandError> {
+        let opening = format!("${{{command}:");
+        let mut inlined = String::with_capacity(contents.len());
+        let mut rest = contents;
+
+        while let Some(start) = rest.find(&opening) {
+            let body_start = start + opening.len();
+            let placeholder = match rest[..start].ends_with('$') {
+                true => None,
+                false => parse_include(command, &rest[start..]),
+            };
+
+            let (placeholder, len) = match placeholder {
+                Some(placeholder) => placeholder,
+                None => {
+                    inlined.push_str(&rest[..body_start]);
+                    rest = &rest[body_start..];
+                    continue;
+                }
+            };
+
+            inlined.push_str(&rest[..start]);
+
+            let path = placeholder.args.as_deref().unwrap_or_default();
+            match (
+                self.include(context, command, path, stack),
+                placeholder.default_value,
+            ) {
+                (Ok(included), _) => inlined.push_str(&included),
+                (Err(err), Some(default_value)) if !err.0.starts_with("Include cycle") => {
+                    context
+                        .diagnostics()
+                        .warn(&format!("{err}, resolving to default value"));
+
+                    inlined.push_str(&default_value);
+                }
+                (Err(err), _) => return Err(err),
+            }
+
+            rest = &rest[start + len..];
+        }
+
+        inlined.push_str(rest);
+        Ok(inlined)
+    }
+}
+
+/// Parses the `command` placeholder `text` starts with, returning it along with its length.
+fn parse_include(command: &str, text: &str) -> Option<(ParsedPlaceholder, usize)> {
+    text.match_indices('}')
+        .map(|(end, _)| end + 1)
+        .find_map(|len| Some((jakarta::parse_placeholder(&text[..len])?, len)))
+        .filter(|(placeholder, _)| {
+            placeholder.command == command
+                && placeholder.field.is_none()
+                && placeholder.filters.is_none()
+        })
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for IncludeCommand {
+    async fn try_process(
+        &mut self,
+        context: &Context,
+        command: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        self.include(context, &command, args.trim(), &mut vec![])
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+
+    fn identifiers(&self) -> Vec<&'static str> {
+        vec!["include"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    struct EchoCommand {}
+
+    #[async_trait]
+    impl JakartaCommand for EchoCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> String {
+            args
+        }
+    }
+
+    #[test]
+    fn it_parses_include_placeholders() {
+        let (placeholder, len) =
+            parse_include("include", "${include: a.yaml :- ${env:X:-{}} } tail").unwrap();
+
+        assert_eq!(len, 35);
+        assert_eq!(placeholder.args.as_deref(), Some("a.yaml"));
+        assert_eq!(placeholder.default_value.as_deref(), Some("${env:X:-{}}"));
+
+        assert!(parse_include("include", "${include:a.yaml | indent}").is_none());
+        assert!(parse_include("include", "${include:a.yaml").is_none());
+        assert!(parse_include("other", "${include:a.yaml}").is_none());
+    }
+
+    fn write_files(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let base_dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&base_dir);
+
+        for (path, contents) in files {
+            let path = base_dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+
+        base_dir
+    }
+
+    #[tokio::test]
+    async fn it_includes_nested_templates() {
+        let base_dir = write_files(
+            "jakarta-include-it-includes-nested-templates",
+            &[
+                (
+                    "partials/header.yaml",
+                    "name: ${echo:app}\n${include:partials/labels.yaml}\n",
+                ),
+                ("partials/labels.yaml", "labels: [${echo:a}, b]\n"),
+            ],
+        );
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert(
+            "include",
+            Arc::new(Mutex::new(IncludeCommand::new(&base_dir))),
+        );
+        commands.insert("echo", Arc::new(Mutex::new(EchoCommand {})));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${include:partials/header.yaml}\n---\n${include:missing.yaml:-{}}\n${include:../etc/passwd:-denied}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            "name: app\nlabels: [a, b]\n---\n{}\ndenied".to_owned()
+        );
+
+        std::fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_rejects_cyclic_and_deep_includes() {
+        let base_dir = write_files(
+            "jakarta-include-it-rejects-cyclic-includes",
+            &[
+                ("a.yaml", "a\n${include:b.yaml:-ignored}"),
+                ("b.yaml", "b\n${include:./a.yaml}"),
+                ("deep.yaml", "${include:deeper.yaml}"),
+                ("deeper.yaml", "bottom"),
+            ],
+        );
+
+        let mut include_cmd = IncludeCommand::new(&base_dir);
+        let err = include_cmd
+            .try_process(
+                &Context::new(),
+                "include".to_owned(),
+                None,
+                "a.yaml".to_owned(),
+                None,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.0.starts_with("Include cycle"), "{err}");
+
+        let err = IncludeCommand::new(&base_dir)
+            .with_max_depth(1)
+            .try_process(
+                &Context::new(),
+                "include".to_owned(),
+                None,
+                "deep.yaml".to_owned(),
+                None,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.0.contains("maximum depth of 1"), "{err}");
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("include", Arc::new(Mutex::new(include_cmd)));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("[${include:a.yaml:-cycle}] [${include:deeper.yaml}]".to_owned())
+            .await;
+
+        assert_eq!(result, "[cycle] [bottom]".to_owned());
+
+        std::fs::remove_dir_all(&base_dir).unwrap();
+    }
+}
@@ -0,0 +1,141 @@
+mod value;
+
+pub use crate::value::{ParseError, Value, ValueExt};
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+
+pub struct JsonCommand {}
+
+#[async_trait]
+impl jakarta::JakartaCommand for JsonCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let path = args.as_str();
+        let field = field.unwrap_or_default();
+
+        let document = std::fs::read_to_string(path)
+            .map_err(|err| CommandError(format!("Could not read file {path:?}: {err}")))?;
+
+        let value = document
+            .parse::<Value>()
+            .map_err(|err| CommandError(format!("Could not parse {path:?} as JSON: {err}")))?;
+
+        value
+            .select(&field)
+            .map(Value::to_text)
+            .ok_or_else(|| CommandError(format!("Could not find {field:?} in {path:?}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    fn write_config(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(
+            &path,
+            r#"{
+                "servers": [
+                    { "host": "alpha.internal", "port": 8080 },
+                    { "host": "beta.internal", "port": 8081, "tags": ["a", "b"] }
+                ],
+                "database": { "pool": { "size": 10, "enabled": true } }
+            }"#,
+        )
+        .unwrap();
+
+        path
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_nested_values() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let json_cmd = Arc::new(Mutex::new(JsonCommand {}));
+        commands.insert("json", json_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let path = write_config("jakarta-json-it-interpolates-nested-values.json");
+
+        let result = jakarta
+            .interpolate_string(format!(
+                "${{json:{0}#database.pool.size}} ${{json:{0}#database.pool.enabled}} ${{json:{0}#database.pool}}",
+                path.display()
+            ))
+            .await;
+
+        assert_eq!(result, r#"10 true {"size":10,"enabled":true}"#.to_owned());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_array_values() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let json_cmd = Arc::new(Mutex::new(JsonCommand {}));
+        commands.insert("json", json_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let path = write_config("jakarta-json-it-interpolates-array-values.json");
+
+        let result = jakarta
+            .interpolate_string(format!(
+                "${{json:{0}#servers.0.host}}:${{json:{0}#/servers/1/port}} ${{json:{0}#servers.1.tags}}",
+                path.display()
+            ))
+            .await;
+
+        assert_eq!(result, r#"alpha.internal:8081 ["a","b"]"#.to_owned());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_for_missing_keys() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let json_cmd = Arc::new(Mutex::new(JsonCommand {}));
+        commands.insert("json", json_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let path = write_config("jakarta-json-it-falls-back-to-default-for-missing-keys.json");
+
+        let result = jakarta
+            .interpolate_string(format!(
+                "${{json:{}#servers.5.host:-localhost}}",
+                path.display()
+            ))
+            .await;
+
+        assert_eq!(result, "localhost".to_owned());
+
+        let result = jakarta
+            .interpolate_string(format!("${{json:{}#database.user}}", path.display()))
+            .await;
+
+        assert_eq!(result, "".to_owned());
+
+        let result = jakarta
+            .interpolate_string("${json:/nonexistent/jakarta.json#a:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "default_value".to_owned());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,73 @@
+pub use serde_json::{Error as ParseError, Value};
+
+/// Path lookups and plain-text rendering shared by the commands that resolve fields of JSON
+/// documents.
+pub trait ValueExt {
+    /// Selects a nested value by a dotted path (`servers.0.host`) or a JSON pointer
+    /// (`/servers/0/host`). Array elements are addressed by their index.
+    fn select(&self, path: &str) -> Option<&Value>;
+
+    /// Strings as they are, everything else as compact JSON.
+    fn to_text(&self) -> String;
+}
+
+impl ValueExt for Value {
+    fn select(&self, path: &str) -> Option<&Value> {
+        let path = path.trim();
+
+        if path.starts_with('/') {
+            return self.pointer(path);
+        }
+
+        path.split('.')
+            .filter(|segment| !segment.is_empty())
+            .try_fold(self, |value, segment| match value {
+                Value::Array(values) => segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|idx| values.get(idx)),
+                value => value.get(segment),
+            })
+    }
+
+    fn to_text(&self) -> String {
+        match self {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_selects_by_dotted_path_and_pointer() {
+        let value = r#"{ "servers": [{ "host": "a" }, { "host": "b" }], "a/b": 1 }"#
+            .parse::<Value>()
+            .unwrap();
+
+        assert_eq!(
+            value.select("servers.1.host").map(Value::to_text),
+            Some("b".to_owned())
+        );
+        assert_eq!(
+            value.select("/servers/0/host").map(Value::to_text),
+            Some("a".to_owned())
+        );
+        assert_eq!(
+            value.select("/a~1b").map(Value::to_text),
+            Some("1".to_owned())
+        );
+        assert_eq!(value.select("servers.2.host"), None);
+        assert_eq!(value.select(""), Some(&value));
+    }
+
+    #[test]
+    fn it_rejects_deeply_nested_documents() {
+        let document = "[".repeat(200_000);
+
+        assert!(document.parse::<Value>().is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use k8s_openapi::{api::core::v1::Secret, ByteString};
+use kube::{Api, Client, Config};
+
+use crate::{K8sError, SecretsClient};
+
+/// The default [`SecretsClient`], reading secrets through kube-rs.
+#[derive(Clone)]
+pub struct KubeClient {
+    client: Client,
+}
+
+impl KubeClient {
+    /// Connects with the local kubeconfig, or the pod's service account when running in a
+    /// cluster.
+    pub async fn try_default() -> Result<Self, K8sError> {
+        let config = Config::infer()
+            .await
+            .map_err(|err| K8sError::Other(err.to_string()))?;
+
+        Self::from_config(config)
+    }
+
+    pub fn from_config(config: Config) -> Result<Self, K8sError> {
+        // kube-rs builds its TLS config from rustls' process-wide crypto provider, which rustls
+        // can't pick on its own once dependencies enable both ring and aws-lc-rs.
+        let _ = rustls::crypto::ring::default_provider().install_default();
+
+        let client = Client::try_from(config).map_err(|err| K8sError::Other(err.to_string()))?;
+
+        Ok(Self::from_client(client))
+    }
+
+    pub fn from_client(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl SecretsClient for KubeClient {
+    async fn get_secret(
+        &self,
+        namespace: &str,
+        name: &str,
+    ) -> Result<BTreeMap<String, ByteString>, K8sError> {
+        let secret = Api::<Secret>::namespaced(self.client.clone(), namespace)
+            .get(name)
+            .await
+            .map_err(|err| match err {
+                kube::Error::Api(status) if status.is_not_found() => {
+                    K8sError::NotFound(name.to_owned())
+                }
+                kube::Error::Api(status) if status.is_forbidden() => {
+                    K8sError::Forbidden(name.to_owned())
+                }
+                err => K8sError::Other(err.to_string()),
+            })?;
+
+        Ok(secret.data.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    fn client(server: &MockServer) -> KubeClient {
+        KubeClient::from_config(Config::new(server.uri().parse().unwrap())).unwrap()
+    }
+
+    fn status(code: u16, reason: &str) -> ResponseTemplate {
+        ResponseTemplate::new(code).set_body_json(serde_json::json!({
+            "kind": "Status",
+            "apiVersion": "v1",
+            "status": "Failure",
+            "message": reason,
+            "reason": reason,
+            "code": code
+        }))
+    }
+
+    #[tokio::test]
+    async fn it_decodes_secret_data() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/namespaces/default/secrets/my-secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "kind": "Secret",
+                "apiVersion": "v1",
+                "metadata": { "name": "my-secret", "namespace": "default" },
+                "data": { "password": "aHVudGVyMg==" }
+            })))
+            .mount(&server)
+            .await;
+
+        let data = client(&server)
+            .get_secret("default", "my-secret")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            data,
+            BTreeMap::from([("password".to_owned(), ByteString(b"hunter2".to_vec()))])
+        );
+    }
+
+    #[tokio::test]
+    async fn it_maps_api_errors() {
+        let server = MockServer::start().await;
+        Mock::given(path("/api/v1/namespaces/default/secrets/missing"))
+            .respond_with(status(404, "NotFound"))
+            .mount(&server)
+            .await;
+        Mock::given(path("/api/v1/namespaces/kube-system/secrets/restricted"))
+            .respond_with(status(403, "Forbidden"))
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+
+        assert!(matches!(
+            client.get_secret("default", "missing").await,
+            Err(K8sError::NotFound(_))
+        ));
+        assert!(matches!(
+            client.get_secret("kube-system", "restricted").await,
+            Err(K8sError::Forbidden(_))
+        ));
+    }
+}
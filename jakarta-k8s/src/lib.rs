@@ -0,0 +1,177 @@
+#[cfg(feature = "kube")]
+mod kube_client;
+
+#[cfg(feature = "kube")]
+pub use crate::kube_client::KubeClient;
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use k8s_openapi::ByteString;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum K8sError {
+    #[error("secret {0:?} not found")]
+    NotFound(String),
+    #[error("access denied to secret {0:?}")]
+    Forbidden(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Reads the `data` of a secret, as the decoded bytes k8s-openapi deserializes it into.
+/// [`KubeClient`] reads it from the cluster.
+#[async_trait]
+pub trait SecretsClient {
+    async fn get_secret(
+        &self,
+        namespace: &str,
+        name: &str,
+    ) -> Result<BTreeMap<String, ByteString>, K8sError>;
+}
+
+pub struct K8sCommand {
+    client: Box<dyn SecretsClient + Send + Sync>,
+    namespace: String,
+}
+
+impl K8sCommand {
+    pub fn new(
+        client: impl SecretsClient + Send + Sync + 'static,
+        namespace: impl Into<String>,
+    ) -> Self {
+        Self {
+            client: Box::new(client),
+            namespace: namespace.into(),
+        }
+    }
+}
+
+fn select_key(data: &BTreeMap<String, ByteString>, key: Option<&str>) -> Option<String> {
+    let value = match key {
+        Some(key) => data.get(key)?,
+        None if data.len() == 1 => data.values().next()?,
+        None => return None,
+    };
+
+    String::from_utf8(value.0.clone()).ok()
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for K8sCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let data = self
+            .client
+            .get_secret(&self.namespace, &args)
+            .await
+            .map_err(|err| CommandError(format!("Could not get secret {args:?}: {err}")))?;
+
+        select_key(&data, field.as_deref())
+            .ok_or_else(|| CommandError(format!("Could not select {field:?} from secret {args:?}")))
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn is_secret(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    fn bytes(value: &str) -> ByteString {
+        ByteString(value.as_bytes().to_vec())
+    }
+
+    struct StubClient {}
+
+    #[async_trait]
+    impl SecretsClient for StubClient {
+        async fn get_secret(
+            &self,
+            namespace: &str,
+            name: &str,
+        ) -> Result<BTreeMap<String, ByteString>, K8sError> {
+            match (namespace, name) {
+                ("default", "my-secret") => Ok(BTreeMap::from([
+                    ("username".to_owned(), bytes("admin")),
+                    ("password".to_owned(), bytes("hunter2")),
+                    ("broken".to_owned(), ByteString(vec![0xff, 0xfe])),
+                ])),
+                ("default", "single") => Ok(BTreeMap::from([("token".to_owned(), bytes("token"))])),
+                _ => Err(K8sError::NotFound(name.to_owned())),
+            }
+        }
+    }
+
+    #[test]
+    fn it_selects_and_decodes_keys() {
+        let data = BTreeMap::from([
+            ("password".to_owned(), bytes("hunter2")),
+            ("other".to_owned(), bytes("other")),
+        ]);
+
+        assert_eq!(
+            select_key(&data, Some("password")),
+            Some("hunter2".to_owned())
+        );
+        assert_eq!(select_key(&data, Some("missing")), None);
+        assert_eq!(select_key(&data, None), None);
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_secret_keys() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let k8s_cmd = Arc::new(Mutex::new(K8sCommand::new(StubClient {}, "default")));
+        commands.insert("k8s", k8s_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${k8s:my-secret#username}:${k8s:my-secret#password} ${k8s:single}".to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "admin:hunter2 token".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_for_missing_secrets_and_keys() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let k8s_cmd = Arc::new(Mutex::new(K8sCommand::new(StubClient {}, "default")));
+        commands.insert("k8s", k8s_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        for template in [
+            "${k8s:missing#password:-default_value}",
+            "${k8s:my-secret#missing:-default_value}",
+            "${k8s:my-secret#broken:-default_value}",
+            "${k8s:my-secret:-default_value}",
+        ] {
+            let result = jakarta.interpolate_string(template.to_owned()).await;
+
+            assert_eq!(result, "default_value".to_owned());
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn as_float(self) -> f64 {
+        match self {
+            Number::Int(value) => value as f64,
+            Number::Float(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Int(value) => write!(f, "{value}"),
+            Number::Float(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    UnexpectedChar(usize, char),
+    UnexpectedEnd,
+    DivisionByZero,
+    Overflow,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::UnexpectedChar(idx, c) => write!(f, "unexpected {c:?} at {idx}"),
+            EvalError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+            EvalError::Overflow => write!(f, "integer overflow"),
+        }
+    }
+}
+
+/// Evaluates `+ - * / %` over integers and floats with the usual precedence, parentheses and
+/// unary signs. Integer operands stay integers, so `7 / 2` is `3` while `7 / 2.0` is `3.5`.
+pub fn evaluate(expression: &str) -> Result<Number, EvalError> {
+    let mut parser = ExprParser {
+        input: expression,
+        idx: 0,
+    };
+
+    let value = parser.expression()?;
+
+    parser.skip_whitespace();
+    match parser.peek() {
+        Some(c) => Err(EvalError::UnexpectedChar(parser.idx, c)),
+        None => Ok(value),
+    }
+}
+
+struct ExprParser<'s> {
+    input: &'s str,
+    idx: usize,
+}
+
+impl ExprParser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.idx..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.idx += 1;
+        }
+    }
+
+    fn next_operator(&mut self, operators: &[char]) -> Option<char> {
+        self.skip_whitespace();
+
+        let operator = self.peek().filter(|c| operators.contains(c))?;
+        self.idx += 1;
+        Some(operator)
+    }
+
+    fn expression(&mut self) -> Result<Number, EvalError> {
+        let mut value = self.term()?;
+
+        while let Some(operator) = self.next_operator(&['+', '-']) {
+            value = apply(operator, value, self.term()?)?;
+        }
+
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<Number, EvalError> {
+        let mut value = self.factor()?;
+
+        while let Some(operator) = self.next_operator(&['*', '/', '%']) {
+            value = apply(operator, value, self.factor()?)?;
+        }
+
+        Ok(value)
+    }
+
+    fn factor(&mut self) -> Result<Number, EvalError> {
+        if let Some(sign) = self.next_operator(&['+', '-']) {
+            let value = self.factor()?;
+
+            return match (sign, value) {
+                ('+', value) => Ok(value),
+                (_, Number::Int(value)) => value
+                    .checked_neg()
+                    .map(Number::Int)
+                    .ok_or(EvalError::Overflow),
+                (_, Number::Float(value)) => Ok(Number::Float(-value)),
+            };
+        }
+
+        if self.next_operator(&['(']).is_some() {
+            let value = self.expression()?;
+
+            return match self.next_operator(&[')']) {
+                Some(_) => Ok(value),
+                None => match self.peek() {
+                    Some(c) => Err(EvalError::UnexpectedChar(self.idx, c)),
+                    None => Err(EvalError::UnexpectedEnd),
+                },
+            };
+        }
+
+        self.number()
+    }
+
+    fn number(&mut self) -> Result<Number, EvalError> {
+        self.skip_whitespace();
+
+        let start = self.idx;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || c == '.' || c == '_')
+        {
+            self.idx += 1;
+        }
+
+        let literal = self.input[start..self.idx].replace('_', "");
+        if literal.is_empty() {
+            return match self.peek() {
+                Some(c) => Err(EvalError::UnexpectedChar(self.idx, c)),
+                None => Err(EvalError::UnexpectedEnd),
+            };
+        }
+
+        let number = match literal.contains('.') {
+            true => literal.parse().ok().map(Number::Float),
+            false => literal.parse().ok().map(Number::Int),
+        };
+
+        number.ok_or(EvalError::UnexpectedChar(start, '.'))
+    }
+}
+
+fn apply(operator: char, lhs: Number, rhs: Number) -> Result<Number, EvalError> {
+    match (lhs, rhs) {
+        (Number::Int(lhs), Number::Int(rhs)) => {
+            let value = match operator {
+                '+' => lhs.checked_add(rhs),
+                '-' => lhs.checked_sub(rhs),
+                '*' => lhs.checked_mul(rhs),
+                _ if rhs == 0 => return Err(EvalError::DivisionByZero),
+                '/' => lhs.checked_div(rhs),
+                _ => lhs.checked_rem(rhs),
+            };
+
+            value.map(Number::Int).ok_or(EvalError::Overflow)
+        }
+        (lhs, rhs) => {
+            let (lhs, rhs) = (lhs.as_float(), rhs.as_float());
+
+            let value = match operator {
+                '+' => lhs + rhs,
+                '-' => lhs - rhs,
+                '*' => lhs * rhs,
+                _ if rhs == 0.0 => return Err(EvalError::DivisionByZero),
+                '/' => lhs / rhs,
+                _ => lhs % rhs,
+            };
+
+            Ok(Number::Float(value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_respects_precedence_and_parentheses() {
+        assert_eq!(evaluate("1 + 2 * 3"), Ok(Number::Int(7)));
+        assert_eq!(evaluate("(1 + 2) * 3"), Ok(Number::Int(9)));
+        assert_eq!(evaluate("10 - 4 - 3"), Ok(Number::Int(3)));
+        assert_eq!(evaluate("-(2 + 3) * -2"), Ok(Number::Int(10)));
+        assert_eq!(evaluate("7 / 2"), Ok(Number::Int(3)));
+        assert_eq!(evaluate("7 / 2.0"), Ok(Number::Float(3.5)));
+        assert_eq!(evaluate("17 % 5 + 1_000"), Ok(Number::Int(1002)));
+    }
+
+    #[test]
+    fn it_rejects_invalid_expressions() {
+        assert_eq!(evaluate("1 / 0"), Err(EvalError::DivisionByZero));
+        assert_eq!(evaluate("1.5 % 0"), Err(EvalError::DivisionByZero));
+        assert_eq!(evaluate("2 +"), Err(EvalError::UnexpectedEnd));
+        assert_eq!(evaluate("(1 + 2"), Err(EvalError::UnexpectedEnd));
+        assert_eq!(evaluate("1 + x"), Err(EvalError::UnexpectedChar(4, 'x')));
+        assert_eq!(evaluate("1 2"), Err(EvalError::UnexpectedChar(2, '2')));
+        assert_eq!(evaluate("1.2.3"), Err(EvalError::UnexpectedChar(0, '.')));
+        assert_eq!(
+            evaluate("9223372036854775807 + 1"),
+            Err(EvalError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+
+mod expr;
+
+/// Evaluates the arithmetic expression in args, e.g. `${math:${env:REPLICAS} * 2}`.
+pub struct MathCommand {}
+
+#[async_trait]
+impl jakarta::JakartaCommand for MathCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        expr::evaluate(&args)
+            .map(|value| value.to_string())
+            .map_err(|err| CommandError(format!("Could not evaluate {args:?}: {err}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_evaluates_expressions() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let math_cmd = Arc::new(Mutex::new(MathCommand {}));
+        commands.insert("math", math_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${math:2 + 3 * 4} ${math:(2 + 3) * 4} ${math:${math:1 + 2} * 2} ${math:1 / 4.0}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "14 20 6 0.25".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_resolves_invalid_expressions_to_the_default() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let math_cmd = Arc::new(Mutex::new(MathCommand {}));
+        commands.insert("math", math_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "[${math:1 / 0:-inf}] [${math:two + 2:-nan}] [${math:3 *}]".to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "[inf] [nan] []".to_owned());
+    }
+}
@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use rand::distr::{Distribution, Uniform};
+
+const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+const ALPHA: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const NUMERIC: &[u8] = b"0123456789";
+const HEX: &[u8] = b"0123456789abcdef";
+
+/// Upper bound on generated values, so a typo can't allocate gigabytes.
+const MAX_LENGTH: usize = 4096;
+
+pub struct RandomCommand {}
+
+fn charset(name: &str) -> Option<&'static [u8]> {
+    match name {
+        "" | "alphanumeric" => Some(ALPHANUMERIC),
+        "alpha" => Some(ALPHA),
+        "numeric" => Some(NUMERIC),
+        "hex" => Some(HEX),
+        _ => None,
+    }
+}
+
+fn random_string(length: usize, charset: &'static [u8]) -> String {
+    let picks = Uniform::new(0, charset.len()).expect("charsets are never empty");
+
+    picks
+        .sample_iter(rand::rng())
+        .take(length)
+        .map(|index| charset[index] as char)
+        .collect()
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for RandomCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let charset_name = field.as_deref().unwrap_or("");
+
+        let (length, charset) = match (args.trim().parse::<usize>(), charset(charset_name)) {
+            (Ok(length), Some(charset)) if length <= MAX_LENGTH => (length, charset),
+            _ => {
+                return Err(CommandError(format!(
+                    "Invalid random specification {args:?}"
+                )))
+            }
+        };
+
+        Ok(random_string(length, charset))
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_generates_alphanumeric_values() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let random_cmd = Arc::new(Mutex::new(RandomCommand {}));
+        commands.insert("random", random_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta.interpolate_string("${random:32}".to_owned()).await;
+
+        assert_eq!(result.len(), 32);
+        assert!(result.chars().all(|c| c.is_ascii_alphanumeric()));
+    }
+
+    #[tokio::test]
+    async fn it_generates_values_from_charsets() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let random_cmd = Arc::new(Mutex::new(RandomCommand {}));
+        commands.insert("random", random_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${random:16#hex}".to_owned())
+            .await;
+
+        assert_eq!(result.len(), 16);
+        assert!(result
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+
+        let result = jakarta
+            .interpolate_string("${random:16#unknown:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "default_value".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_rejects_oversized_values() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let random_cmd = Arc::new(Mutex::new(RandomCommand {}));
+        commands.insert("random", random_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(format!("${{random:{MAX_LENGTH}}}"))
+            .await;
+        assert_eq!(result.len(), MAX_LENGTH);
+
+        let result = jakarta
+            .interpolate_string("${random:99999999999:-too_long}".to_owned())
+            .await;
+        assert_eq!(result, "too_long".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_generates_distinct_values() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let random_cmd = Arc::new(Mutex::new(RandomCommand {}));
+        commands.insert("random", random_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let first = jakarta.interpolate_string("${random:32}".to_owned()).await;
+        let second = jakarta.interpolate_string("${random:32}".to_owned()).await;
+
+        assert_ne!(first, second);
+        assert!(!random_cmd.lock().await.cacheable());
+    }
+}
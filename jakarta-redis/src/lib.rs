@@ -0,0 +1,287 @@
+mod resp;
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use thiserror::Error;
+use tokio::{
+    io::{AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+
+use crate::resp::Reply;
+
+#[derive(Error, Debug)]
+pub enum RedisError {
+    #[error("invalid connection string {0:?}")]
+    InvalidConnectionString(String),
+    #[error("malformed reply")]
+    MalformedReply,
+    #[error("connection closed")]
+    ConnectionClosed,
+    #[error("server error: {0}")]
+    Server(String),
+    #[error("io error")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConnectionInfo {
+    address: String,
+    username: Option<String>,
+    password: Option<String>,
+    database: Option<u32>,
+}
+
+impl ConnectionInfo {
+    fn parse(connection_string: &str) -> Result<Self, RedisError> {
+        let invalid = || RedisError::InvalidConnectionString(connection_string.to_owned());
+
+        let rest = connection_string
+            .strip_prefix("redis://")
+            .unwrap_or(connection_string);
+        let (rest, database) = match rest.split_once('/') {
+            Some((rest, "")) => (rest, None),
+            Some((rest, database)) => (rest, Some(database.parse().map_err(|_| invalid())?)),
+            None => (rest, None),
+        };
+        let (credentials, host) = match rest.rsplit_once('@') {
+            Some((credentials, host)) => (Some(credentials), host),
+            None => (None, rest),
+        };
+        let (username, password) = match credentials {
+            Some(credentials) => match credentials.split_once(':') {
+                Some((username, password)) => (
+                    Some(username).filter(|username| !username.is_empty()),
+                    Some(password),
+                ),
+                None => (None, Some(credentials)),
+            },
+            None => (None, None),
+        };
+
+        if host.is_empty() {
+            return Err(invalid());
+        }
+
+        let address = if host.rsplit_once(':').is_some() {
+            host.to_owned()
+        } else {
+            format!("{host}:6379")
+        };
+
+        Ok(Self {
+            address,
+            username: username.map(str::to_owned),
+            password: password.map(str::to_owned),
+            database,
+        })
+    }
+}
+
+fn request<'a>(key: &'a str, field: Option<&'a str>) -> Vec<&'a str> {
+    match field {
+        Some(field) => vec!["HGET", key, field],
+        None => vec!["GET", key],
+    }
+}
+
+pub struct RedisCommand {
+    info: ConnectionInfo,
+    connection: Option<BufReader<TcpStream>>,
+}
+
+impl RedisCommand {
+    pub fn new(connection_string: &str) -> Result<Self, RedisError> {
+        Ok(Self {
+            info: ConnectionInfo::parse(connection_string)?,
+            connection: None,
+        })
+    }
+
+    async fn connect(&self) -> Result<BufReader<TcpStream>, RedisError> {
+        let mut connection = BufReader::new(TcpStream::connect(&self.info.address).await?);
+
+        if let Some(password) = &self.info.password {
+            let auth = match &self.info.username {
+                Some(username) => vec!["AUTH", username, password],
+                None => vec!["AUTH", password],
+            };
+            send(&mut connection, &auth).await?;
+        }
+
+        if let Some(database) = self.info.database {
+            send(&mut connection, &["SELECT", &database.to_string()]).await?;
+        }
+
+        Ok(connection)
+    }
+
+    async fn query(&mut self, args: &[&str]) -> Result<Option<String>, RedisError> {
+        let mut connection = match self.connection.take() {
+            Some(connection) => connection,
+            None => self.connect().await?,
+        };
+
+        let reply = send(&mut connection, args).await?;
+        self.connection = Some(connection);
+
+        match reply {
+            Reply::Bulk(Some(data)) => Ok(Some(String::from_utf8_lossy(&data).into_owned())),
+            Reply::Bulk(None) => Ok(None),
+            Reply::Simple(value) => Ok(Some(value)),
+            Reply::Integer(value) => Ok(Some(value.to_string())),
+            _ => Err(RedisError::MalformedReply),
+        }
+    }
+}
+
+async fn send(connection: &mut BufReader<TcpStream>, args: &[&str]) -> Result<Reply, RedisError> {
+    connection.get_mut().write_all(&resp::encode(args)).await?;
+
+    match resp::read_reply(connection).await? {
+        Reply::Error(message) => Err(RedisError::Server(message)),
+        reply => Ok(reply),
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for RedisCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        match self.query(&request(&args, field.as_deref())).await {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Err(CommandError(format!("Redis key {args:?} does not exist"))),
+            Err(err) => Err(CommandError(format!(
+                "Could not get redis key {args:?}: {err}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::{io::AsyncReadExt, net::TcpListener, sync::Mutex};
+
+    use super::*;
+
+    #[test]
+    fn it_dispatches_get_or_hget_on_field_presence() {
+        assert_eq!(request("session:count", None), vec!["GET", "session:count"]);
+        assert_eq!(
+            request("user:42", Some("email")),
+            vec!["HGET", "user:42", "email"]
+        );
+    }
+
+    #[test]
+    fn it_parses_connection_strings() {
+        assert_eq!(
+            ConnectionInfo::parse("redis://:secret@cache.internal/2").unwrap(),
+            ConnectionInfo {
+                address: "cache.internal:6379".to_owned(),
+                username: None,
+                password: Some("secret".to_owned()),
+                database: Some(2),
+            }
+        );
+        assert_eq!(
+            ConnectionInfo::parse("127.0.0.1:6380").unwrap(),
+            ConnectionInfo {
+                address: "127.0.0.1:6380".to_owned(),
+                username: None,
+                password: None,
+                database: None,
+            }
+        );
+        assert!(ConnectionInfo::parse("redis://").is_err());
+        assert!(ConnectionInfo::parse("redis://localhost/db").is_err());
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_from_a_mock_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 1024];
+
+            loop {
+                let read = stream.read(&mut buffer).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+
+                let request = &buffer[..read];
+                let reply: &[u8] = if request == resp::encode(&["GET", "session:count"]) {
+                    b"$2\r\n17\r\n"
+                } else if request == resp::encode(&["HGET", "user:42", "email"]) {
+                    b"$16\r\njane@example.com\r\n"
+                } else {
+                    b"$-1\r\n"
+                };
+                stream.write_all(reply).await.unwrap();
+            }
+        });
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let redis_cmd = Arc::new(Mutex::new(
+            RedisCommand::new(&format!("redis://{address}")).unwrap(),
+        ));
+        commands.insert("redis", redis_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${redis:session:count} ${redis:user:42#email}".to_owned())
+            .await;
+        assert_eq!(result, "17 jane@example.com".to_owned());
+
+        let result = jakarta
+            .interpolate_string("${redis:user:43#email:-default_value}".to_owned())
+            .await;
+        assert_eq!(result, "default_value".to_owned());
+    }
+
+    #[cfg(feature = "redis-tests")]
+    #[tokio::test]
+    async fn it_interpolates_from_a_local_redis() {
+        let mut connection = RedisCommand::new("redis://127.0.0.1:6379").unwrap();
+        connection
+            .query(&["SET", "jakarta:test:key", "value"])
+            .await
+            .unwrap();
+        connection
+            .query(&["HSET", "jakarta:test:hash", "field", "hash_value"])
+            .await
+            .unwrap();
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let redis_cmd = Arc::new(Mutex::new(connection));
+        commands.insert("redis", redis_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${redis:jakarta:test:key} ${redis:jakarta:test:hash#field}".to_owned(),
+            )
+            .await;
+        assert_eq!(result, "value hash_value".to_owned());
+
+        let result = jakarta
+            .interpolate_string("${redis:jakarta:test:missing:-default_value}".to_owned())
+            .await;
+        assert_eq!(result, "default_value".to_owned());
+    }
+}
@@ -0,0 +1,122 @@
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+
+use crate::RedisError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    Simple(String),
+    Error(String),
+    Integer(i64),
+    Bulk(Option<Vec<u8>>),
+    Array(Option<Vec<Reply>>),
+}
+
+pub fn encode(args: &[&str]) -> Vec<u8> {
+    let mut encoded = format!("*{}\r\n", args.len()).into_bytes();
+
+    for arg in args {
+        encoded.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        encoded.extend_from_slice(arg.as_bytes());
+        encoded.extend_from_slice(b"\r\n");
+    }
+
+    encoded
+}
+
+pub async fn read_reply<R: AsyncBufRead + Unpin + Send>(
+    reader: &mut R,
+) -> Result<Reply, RedisError> {
+    let line = read_line(reader).await?;
+    let (kind, rest) = line.split_at(1);
+
+    match kind {
+        "+" => Ok(Reply::Simple(rest.to_owned())),
+        "-" => Ok(Reply::Error(rest.to_owned())),
+        ":" => Ok(Reply::Integer(parse_length(rest)?)),
+        "$" => match parse_length(rest)? {
+            -1 => Ok(Reply::Bulk(None)),
+            length if length >= 0 => {
+                let mut data = vec![0; length as usize + 2];
+                reader.read_exact(&mut data).await?;
+                data.truncate(length as usize);
+
+                Ok(Reply::Bulk(Some(data)))
+            }
+            _ => Err(RedisError::MalformedReply),
+        },
+        "*" => match parse_length(rest)? {
+            -1 => Ok(Reply::Array(None)),
+            length if length >= 0 => {
+                let mut items = Vec::with_capacity(length as usize);
+                for _ in 0..length {
+                    items.push(Box::pin(read_reply(reader)).await?);
+                }
+
+                Ok(Reply::Array(Some(items)))
+            }
+            _ => Err(RedisError::MalformedReply),
+        },
+        _ => Err(RedisError::MalformedReply),
+    }
+}
+
+async fn read_line<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<String, RedisError> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Err(RedisError::ConnectionClosed);
+    }
+
+    match line.strip_suffix("\r\n") {
+        Some(line) if !line.is_empty() => Ok(line.to_owned()),
+        _ => Err(RedisError::MalformedReply),
+    }
+}
+
+fn parse_length(value: &str) -> Result<i64, RedisError> {
+    value.parse().map_err(|_| RedisError::MalformedReply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_encodes_commands() {
+        assert_eq!(
+            encode(&["HGET", "user:42", "email"]),
+            b"*3\r\n$4\r\nHGET\r\n$7\r\nuser:42\r\n$5\r\nemail\r\n".to_vec()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_reads_replies() {
+        let mut input: &[u8] =
+            b"+OK\r\n-ERR wrong\r\n:42\r\n$5\r\nhello\r\n$-1\r\n*2\r\n$1\r\na\r\n:1\r\n";
+
+        assert_eq!(
+            read_reply(&mut input).await.unwrap(),
+            Reply::Simple("OK".to_owned())
+        );
+        assert_eq!(
+            read_reply(&mut input).await.unwrap(),
+            Reply::Error("ERR wrong".to_owned())
+        );
+        assert_eq!(read_reply(&mut input).await.unwrap(), Reply::Integer(42));
+        assert_eq!(
+            read_reply(&mut input).await.unwrap(),
+            Reply::Bulk(Some(b"hello".to_vec()))
+        );
+        assert_eq!(read_reply(&mut input).await.unwrap(), Reply::Bulk(None));
+        assert_eq!(
+            read_reply(&mut input).await.unwrap(),
+            Reply::Array(Some(vec![
+                Reply::Bulk(Some(b"a".to_vec())),
+                Reply::Integer(1)
+            ]))
+        );
+        assert!(matches!(
+            read_reply(&mut input).await,
+            Err(RedisError::ConnectionClosed)
+        ));
+    }
+}
@@ -1,51 +1,210 @@
+use std::{collections::HashMap, path::PathBuf};
+
 use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+
+#[cfg(windows)]
+const DEFAULT_SHELL: (&str, &str) = ("cmd", "/C");
+#[cfg(not(windows))]
+const DEFAULT_SHELL: (&str, &str) = ("sh", "-c");
+
+/// Runs `cmd`, unless process execution was compiled out by disabling the `exec` feature.
+#[cfg(feature = "exec")]
+async fn execute(cmd: std::process::Command) -> std::io::Result<std::process::Output> {
+    tokio::process::Command::from(cmd).output().await
+}
+
+#[cfg(not(feature = "exec"))]
+async fn execute(_: std::process::Command) -> std::io::Result<std::process::Output> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "process execution is disabled, enable the `exec` feature of jakarta-sh",
+    ))
+}
+
+type Validator = Box<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Runs args through a shell, resolving to its trimmed stdout. The field selects `raw` (untrimmed)
+/// stdout, `stderr`, the 1-based `line:N` or the value of a `kv:KEY` line.
+pub struct ShCommand {
+    shell: String,
+    shell_flag: String,
+    validator: Option<Validator>,
+    envs: HashMap<String, String>,
+    current_dir: Option<PathBuf>,
+    env_clear: bool,
+}
+
+impl Default for ShCommand {
+    fn default() -> Self {
+        Self {
+            shell: DEFAULT_SHELL.0.to_owned(),
+            shell_flag: DEFAULT_SHELL.1.to_owned(),
+            validator: None,
+            envs: HashMap::new(),
+            current_dir: None,
+            env_clear: false,
+        }
+    }
+}
+
+impl ShCommand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs args through `shell` instead of `sh -c` (`cmd /C` on Windows), e.g. `with_shell("bash", "-c")` or
+    /// `with_shell("cmd", "/C")`.
+    pub fn with_shell(mut self, shell: impl Into<String>, flag: impl Into<String>) -> Self {
+        self.shell = shell.into();
+        self.shell_flag = flag.into();
+        self
+    }
+
+    /// Only runs args for which `validator` returns true, everything else resolves to the default.
+    pub fn with_validator(mut self, validator: Validator) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.envs.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn with_envs(mut self, envs: HashMap<String, String>) -> Self {
+        self.envs.extend(envs);
+        self
+    }
+
+    pub fn with_current_dir(mut self, current_dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(current_dir.into());
+        self
+    }
 
-pub struct ShCommand {}
+    /// Starts processes without the inherited environment, only the configured env vars are set.
+    pub fn with_env_clear(mut self) -> Self {
+        self.env_clear = true;
+        self
+    }
+}
 
 #[async_trait]
 impl jakarta::JakartaCommand for ShCommand {
-    async fn process(
+    async fn try_process(
         &mut self,
+        _: &Context,
         _command: String,
+        _: Option<String>,
         args: String,
-        default_value: Option<String>,
-    ) -> String {
-        let cmd = std::process::Command::new("sh")
-            .arg("-c")
-            .arg(args.clone())
-            .output();
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        if let Some(validator) = &self.validator {
+            if !validator(&args) {
+                return Err(CommandError(format!(
+                    "Process {args:?} was rejected by the validator"
+                )));
+            }
+        }
+
+        let mut cmd = std::process::Command::new(&self.shell);
+        cmd.arg(&self.shell_flag).arg(args.clone());
+
+        if self.env_clear {
+            cmd.env_clear();
+        }
+        cmd.envs(&self.envs);
+        if let Some(current_dir) = &self.current_dir {
+            cmd.current_dir(current_dir);
+        }
 
-        match cmd {
-            Ok(cmd) => String::from_utf8(cmd.stdout).unwrap_or_else(|_| {
-                tracing::warn!(
-                    "Could not obtain stdout from process {args:?}, resolving to default value"
-                );
+        let output = execute(cmd)
+            .await
+            .map_err(|err| CommandError(format!("Failed to execute process {args:?}: {err}")))?;
 
-                default_value.unwrap_or_else(|| "".to_owned())
-            }),
-            Err(err) => {
-                tracing::warn!("Failed to execute process {args:?}: {err}");
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
 
-                default_value.unwrap_or_else(|| "".to_owned())
-            }
+            return Err(CommandError(format!(
+                "Process {args:?} exited with {}: {}",
+                output.status,
+                stderr.trim_end()
+            )));
         }
+
+        let (stream, bytes) = match field.as_deref() {
+            Some("stderr") => ("stderr", output.stderr),
+            _ => ("stdout", output.stdout),
+        };
+
+        let stdout = String::from_utf8(bytes).map_err(|_| {
+            CommandError(format!("Could not obtain {stream} from process {args:?}"))
+        })?;
+
+        let field = match field {
+            Some(field) if field == "raw" => return Ok(stdout),
+            Some(field) if field != "stderr" => field,
+            _ => return Ok(jakarta::trim_trailing_newline(stdout)),
+        };
+
+        let Some(selector) = jakarta::FieldSelector::parse(&field) else {
+            return Err(CommandError(format!(
+                "Unsupported field {field:?} for process {args:?}, expected raw, stderr, line:N or kv:KEY"
+            )));
+        };
+
+        selector.select(&stdout).ok_or_else(|| {
+            CommandError(format!(
+                "Could not select line {field:?} from process {args:?}"
+            ))
+        })
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn is_secret(&self) -> bool {
+        true
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, sync::Arc};
+    use std::sync::Arc;
 
     use jakarta::{Jakarta, JakartaCommand};
     use tokio::sync::Mutex;
 
     use super::*;
 
+    #[cfg(all(windows, feature = "exec"))]
+    #[tokio::test]
+    async fn it_runs_commands_through_cmd_on_windows() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:echo hi}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd hi".to_owned());
+    }
+
+    #[cfg(feature = "exec")]
     #[tokio::test]
     async fn it_runs_shell_commands() {
         let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
 
-        let sh_cmd = Arc::new(Mutex::new(ShCommand {}));
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
         commands.insert("sh", sh_cmd.clone());
         let jakarta = Jakarta::new(commands).unwrap();
 
@@ -55,4 +214,321 @@ mod tests {
 
         assert_eq!(result, "asd 1".to_owned());
     }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_selects_lines_from_stdout() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:printf 'a\\nb\\n'#line:2}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd b".to_owned());
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:printf 'a\\nb\\n'#line:3:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd default_value".to_owned());
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:printf 'a\\nb\\n'#1:-unsupported}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd unsupported".to_owned());
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_runs_shell_pipelines() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                r#"${sh:echo a \| tr a b} ${sh:"echo a|tr a b" | upper}"#.to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "b B".to_owned());
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_runs_repeated_commands_every_time() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let log = std::env::temp_dir().join(format!("jakarta-sh-count-{}", std::process::id()));
+        let _ = std::fs::remove_file(&log);
+        let placeholder = format!("${{sh:echo x >> {0}; wc -l < {0} | trim}}", log.display());
+
+        let result = jakarta
+            .interpolate_string(format!("{placeholder} {placeholder} {placeholder}"))
+            .await;
+        let _ = std::fs::remove_file(&log);
+
+        assert_eq!(result, "1 2 3".to_owned());
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_does_not_expand_shell_output() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:printf '\\044\\173sh:printf 1\\175'}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd ${sh:printf 1}".to_owned());
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_runs_quoted_commands_with_braces() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(r#"asd ${sh:"printf '%s' '{\"k\":\"v\"}'"}"#.to_owned())
+            .await;
+
+        assert_eq!(result, r#"asd {"k":"v"}"#.to_owned());
+
+        let result = jakarta
+            .interpolate_string(r##"asd ${sh:"printf '{\"a\":1}\n}\n'"#line:2}"##.to_owned())
+            .await;
+
+        assert_eq!(result, "asd }".to_owned());
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_skips_commands_rejected_by_the_validator() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new().with_validator(Box::new(
+            |args: &str| args.split_whitespace().next() == Some("printf"),
+        ))));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:printf 1}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd 1".to_owned());
+
+        let dir = std::env::temp_dir().join(format!("jakarta-sh-validator-{}", std::process::id()));
+        let result = jakarta
+            .interpolate_string(format!("asd ${{sh:mkdir {}:-denied}}", dir.display()))
+            .await;
+
+        assert_eq!(result, "asd denied".to_owned());
+        assert!(!dir.exists());
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_applies_the_configured_environment() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        std::env::set_var("JAKARTA_SH_INHERITED", "inherited");
+        let sh_cmd = Arc::new(Mutex::new(
+            ShCommand::new()
+                .with_env("JAKARTA_SH_GREETING", "hello")
+                .with_env_clear(),
+        ));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                r#"asd ${sh:"printf '%s-%s' \"$JAKARTA_SH_GREETING\" \"$JAKARTA_SH_INHERITED\""}"#
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "asd hello-".to_owned());
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_runs_in_the_configured_directory() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let dir = std::env::temp_dir().canonicalize().unwrap();
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new().with_current_dir(&dir)));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:pwd -P}".to_owned())
+            .await;
+
+        assert_eq!(result, format!("asd {}", dir.display()));
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_runs_commands_in_a_custom_shell() {
+        let bash_available = std::process::Command::new("bash")
+            .arg("-c")
+            .arg("true")
+            .status()
+            .is_ok_and(|status| status.success());
+        if !bash_available {
+            return;
+        }
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new().with_shell("bash", "-c")));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                r#"asd ${sh:"value=abcdef; printf '%s' \"${value^^}\""}"#.to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "asd ABCDEF".to_owned());
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_resolves_failing_commands_to_the_default() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:false:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd default_value".to_owned());
+
+        let result = jakarta
+            .interpolate_string(
+                "asd ${sh:printf out; printf err >&2; exit 3:-default_value}".to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "asd default_value".to_owned());
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:true:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd default_value".to_owned());
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_captures_stderr() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:printf out; printf err >&2#stderr}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd err".to_owned());
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:printf out; printf err >&2}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd out".to_owned());
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_trims_trailing_newlines_unless_raw() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("[${sh:echo 1}] [${sh:printf 1}]".to_owned())
+            .await;
+
+        assert_eq!(result, "[1] [1]".to_owned());
+
+        let result = jakarta
+            .interpolate_string("[${sh:echo 1#raw}] [${sh:printf 1#raw}]".to_owned())
+            .await;
+
+        assert_eq!(result, "[1\n] [1]".to_owned());
+    }
+
+    #[cfg(feature = "exec")]
+    #[tokio::test]
+    async fn it_selects_lines_and_keys() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${sh:printf 'a\\nb\\nc\\n'#line:3} ${sh:printf 'USER=admin\\nPORT=5432\\n'#kv:PORT}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "c 5432".to_owned());
+
+        let result = jakarta
+            .interpolate_string(
+                "${sh:printf 'a\\n'#line:2:-no_line} ${sh:printf 'USER=admin\\n'#kv:PORT:-no_key}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "no_line no_key".to_owned());
+    }
+
+    #[cfg(not(feature = "exec"))]
+    #[tokio::test]
+    async fn it_does_not_execute_processes_without_the_exec_feature() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let sh_cmd = Arc::new(Mutex::new(ShCommand::new()));
+        commands.insert("sh", sh_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("asd ${sh:printf 1:-default_value} ${sh:printf 1}".to_owned())
+            .await;
+
+        assert_eq!(result, "asd default_value ".to_owned());
+    }
 }
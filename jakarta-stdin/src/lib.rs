@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Resolves `${stdin}` to the piped input, or any other reader passed to `from_reader`. The input
+/// is read to the end on first use and buffered, so every later placeholder gets the same value.
+/// Placeholders in the input are not interpolated.
+pub struct StdinCommand<R = tokio::io::Stdin> {
+    reader: Option<R>,
+    buffer: Option<String>,
+}
+
+impl StdinCommand {
+    pub fn new() -> Self {
+        Self::from_reader(tokio::io::stdin())
+    }
+}
+
+impl Default for StdinCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: AsyncRead + Unpin + Send> StdinCommand<R> {
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            reader: Some(reader),
+            buffer: None,
+        }
+    }
+
+    async fn read(&mut self) -> Result<&str, std::io::Error> {
+        if let Some(mut reader) = self.reader.take() {
+            let mut bytes = vec![];
+            reader.read_to_end(&mut bytes).await?;
+
+            self.buffer = Some(jakarta::trim_trailing_newline(
+                String::from_utf8_lossy(&bytes).into_owned(),
+            ));
+        }
+
+        Ok(self.buffer.as_deref().unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl<R: AsyncRead + Unpin + Send> jakarta::JakartaCommand for StdinCommand<R> {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        _: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        self.read()
+            .await
+            .map(str::to_owned)
+            .map_err(|err| CommandError(format!("Could not read stdin: {err}")))
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn identifiers(&self) -> Vec<&'static str> {
+        vec!["stdin"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_buffers_the_input() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let stdin_cmd = StdinCommand::from_reader(&b"piped ${value}\n"[..]);
+        commands.insert("stdin", Arc::new(Mutex::new(stdin_cmd)));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("[${stdin}] [${stdin:again}]".to_owned())
+            .await;
+        assert_eq!(result, "[piped ${value}] [piped ${value}]".to_owned());
+
+        let result = jakarta.interpolate_string("${stdin}".to_owned()).await;
+        assert_eq!(result, "piped ${value}".to_owned());
+    }
+}
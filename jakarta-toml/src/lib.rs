@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use jakarta::{Jakarta, JakartaError};
+use toml::{Table, Value};
+
+/// Interpolates the string values of a TOML document in place and re-serializes it, so resolved
+/// values containing quotes, brackets or newlines are escaped as needed and can't break the
+/// document. Keys are left as is. Comments and the original formatting aren't preserved.
+#[async_trait]
+pub trait InterpolateToml {
+    async fn interpolate_toml(&self, toml: &str) -> Result<String, JakartaError>;
+}
+
+#[async_trait]
+impl InterpolateToml for Jakarta<'_> {
+    async fn interpolate_toml(&self, toml: &str) -> Result<String, JakartaError> {
+        let table = toml
+            .parse::<Table>()
+            .map_err(|err| JakartaError::ParseError {
+                message: err.message().to_owned(),
+                span: err.span().unwrap_or_default(),
+            })?;
+        let table = interpolate_table(self, table).await?;
+
+        toml::to_string(&table).map_err(|err| JakartaError::ParseError {
+            message: err.to_string(),
+            span: 0..0,
+        })
+    }
+}
+
+async fn interpolate_table(jakarta: &Jakarta<'_>, table: Table) -> Result<Table, JakartaError> {
+    let mut interpolated_table = Table::new();
+    for (key, value) in table {
+        interpolated_table.insert(key, Box::pin(interpolate_value(jakarta, value)).await?);
+    }
+
+    Ok(interpolated_table)
+}
+
+async fn interpolate_value(jakarta: &Jakarta<'_>, value: Value) -> Result<Value, JakartaError> {
+    Ok(match value {
+        Value::String(value) => Value::String(jakarta.try_interpolate_string(value).await?),
+        Value::Array(values) => {
+            let mut interpolated_values = Vec::with_capacity(values.len());
+            for value in values {
+                interpolated_values.push(Box::pin(interpolate_value(jakarta, value)).await?);
+            }
+
+            Value::Array(interpolated_values)
+        }
+        Value::Table(table) => Value::Table(interpolate_table(jakarta, table).await?),
+        value => value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::JakartaCommand;
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    struct LookupCommand {}
+
+    #[async_trait]
+    impl JakartaCommand for LookupCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> String {
+            match args.as_str() {
+                "QUOTED" => r#"say "hi" [now]"#.to_owned(),
+                "APOSTROPHE" => "it's".to_owned(),
+                "LINES" => "line 1\nline 2".to_owned(),
+                args => args.to_lowercase(),
+            }
+        }
+    }
+
+    fn jakarta() -> Jakarta<'static> {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("env", Arc::new(Mutex::new(LookupCommand {})));
+
+        Jakarta::new(commands).unwrap()
+    }
+
+    fn parse(toml: &str) -> Table {
+        toml.parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_tables_and_arrays() {
+        let toml = r#"# ${env:COMMENT}
+title = "${env:TITLE}"
+
+[database."${env:KEY}"]
+hosts = [
+  "${env:A}", # ${env:COMMENT}
+  '${env:B}',
+]
+port = 5432
+point = { x = "${env:X}", "${env:Y}" = '${env:Y}' }
+
+[[servers]]
+name = "plain"
+"#;
+
+        let result = jakarta().interpolate_toml(toml).await.unwrap();
+
+        assert_eq!(
+            parse(&result),
+            parse(
+                r#"title = "title"
+
+[database."${env:KEY}"]
+hosts = ["a", "b"]
+port = 5432
+point = { x = "x", "${env:Y}" = "y" }
+
+[[servers]]
+name = "plain"
+"#
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn it_requotes_values() {
+        let toml = r#"quoted = "${env:QUOTED}"
+literal = '${env:APOSTROPHE}'
+multiline = """
+${env:LINES}"""
+unchanged = 'C:\path\to'
+"#;
+
+        let result = jakarta().interpolate_toml(toml).await.unwrap();
+
+        assert_eq!(
+            parse(&result),
+            parse(
+                r#"quoted = "say \"hi\" [now]"
+literal = "it's"
+multiline = "line 1\nline 2"
+unchanged = 'C:\path\to'
+"#
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_malformed_documents() {
+        let result = jakarta().interpolate_toml("a = [1, 2\n").await;
+
+        assert!(matches!(result, Err(JakartaError::ParseError { .. })));
+    }
+}
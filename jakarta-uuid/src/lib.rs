@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use uuid::Uuid;
+
+pub struct UuidCommand {}
+
+#[async_trait]
+impl jakarta::JakartaCommand for UuidCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let uuid = match args.trim() {
+            "" | "v4" => Uuid::new_v4(),
+            "v7" => Uuid::now_v7(),
+            version => {
+                return Err(CommandError(format!(
+                    "Unsupported UUID version {version:?}"
+                )))
+            }
+        };
+
+        Ok(uuid.hyphenated().to_string())
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{Jakarta, JakartaCommand};
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    fn assert_uuid(uuid: &str, version: char) {
+        let groups: Vec<&str> = uuid.split('-').collect();
+
+        assert_eq!(
+            groups.iter().map(|group| group.len()).collect::<Vec<_>>(),
+            vec![8, 4, 4, 4, 12]
+        );
+        assert!(groups
+            .iter()
+            .all(|group| group.chars().all(|c| c.is_ascii_hexdigit())));
+        assert_eq!(groups[2].chars().next(), Some(version));
+        assert!(matches!(
+            groups[3].chars().next(),
+            Some('8' | '9' | 'a' | 'b')
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_generates_v4_uuids() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let uuid_cmd = Arc::new(Mutex::new(UuidCommand {}));
+        commands.insert("uuid", uuid_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta.interpolate_string("${uuid}".to_owned()).await;
+        assert_uuid(&result, '4');
+
+        let result = jakarta.interpolate_string("${uuid:v4}".to_owned()).await;
+        assert_uuid(&result, '4');
+    }
+
+    #[tokio::test]
+    async fn it_generates_v7_uuids() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let uuid_cmd = Arc::new(Mutex::new(UuidCommand {}));
+        commands.insert("uuid", uuid_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let first = jakarta.interpolate_string("${uuid:v7}".to_owned()).await;
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = jakarta.interpolate_string("${uuid:v7}".to_owned()).await;
+
+        assert_uuid(&first, '7');
+        assert_uuid(&second, '7');
+        assert!(first[..13] < second[..13]);
+    }
+
+    #[tokio::test]
+    async fn it_generates_unique_uuids_per_placeholder() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let uuid_cmd = Arc::new(Mutex::new(UuidCommand {}));
+        commands.insert("uuid", uuid_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${uuid} ${uuid}".to_owned())
+            .await;
+        let (first, second) = result.split_once(' ').unwrap();
+
+        assert_uuid(first, '4');
+        assert_uuid(second, '4');
+        assert_ne!(first, second);
+
+        let result = jakarta
+            .interpolate_string("${uuid:v9:-default_value}".to_owned())
+            .await;
+        assert_eq!(result, "default_value".to_owned());
+    }
+}
@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+
+use crate::{Method, VaultClient, VaultError, VaultResponse};
+
+/// The default [`VaultClient`], sending requests with reqwest and rustls to the vault at
+/// `address`, e.g. `https://vault.internal:8200`.
+#[derive(Clone)]
+pub struct ReqwestClient {
+    client: reqwest::Client,
+    address: String,
+}
+
+impl ReqwestClient {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self::from_client(reqwest::Client::new(), address)
+    }
+
+    /// Sends requests through a preconfigured client, e.g. one trusting the vault's CA.
+    pub fn from_client(client: reqwest::Client, address: impl Into<String>) -> Self {
+        Self {
+            client,
+            address: address.into().trim_end_matches('/').to_owned(),
+        }
+    }
+}
+
+#[async_trait]
+impl VaultClient for ReqwestClient {
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        token: &str,
+        body: Option<&str>,
+    ) -> Result<VaultResponse, VaultError> {
+        let url = format!("{}/{}", self.address, path.trim_start_matches('/'));
+        let request = match method {
+            Method::Get => self.client.get(url),
+            Method::Post => self.client.post(url),
+        }
+        .header("X-Vault-Token", token);
+
+        let request = match body {
+            Some(body) => request
+                .header("Content-Type", "application/json")
+                .body(body.to_owned()),
+            None => request,
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(|err| VaultError::Other(err.to_string()))?;
+
+        Ok(VaultResponse {
+            status: response.status().as_u16(),
+            body: response
+                .text()
+                .await
+                .map_err(|err| VaultError::Other(err.to_string()))?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{body_string, header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_sends_authenticated_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/app"))
+            .and(query_param("version", "2"))
+            .and(header("X-Vault-Token", "root"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"data":{}}"#))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/auth/token/renew-self"))
+            .and(body_string("{}"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let client = ReqwestClient::new(format!("{}/", server.uri()));
+
+        assert_eq!(
+            client
+                .send(Method::Get, "v1/secret/data/app?version=2", "root", None)
+                .await
+                .unwrap(),
+            VaultResponse {
+                status: 200,
+                body: r#"{"data":{}}"#.to_owned()
+            }
+        );
+        assert_eq!(
+            client
+                .send(Method::Post, "v1/auth/token/renew-self", "root", Some("{}"))
+                .await
+                .unwrap()
+                .status,
+            403
+        );
+    }
+}
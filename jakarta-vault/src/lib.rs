@@ -0,0 +1,631 @@
+mod client;
+mod token;
+
+pub use crate::client::ReqwestClient;
+
+use std::{collections::HashMap, time::Duration};
+
+use async_trait::async_trait;
+use jakarta::{CommandError, Context};
+use jakarta_json::{Value, ValueExt};
+use thiserror::Error;
+
+use crate::token::Token;
+
+#[derive(Error, Debug)]
+pub enum VaultError {
+    #[error("unsupported command {0:?}, expected kv1, kv2 or db")]
+    UnsupportedCommand(String),
+    #[error("invalid secret path {0:?}")]
+    InvalidPath(String),
+    #[error("vault returned status {0}")]
+    Status(u16),
+    #[error("malformed response")]
+    MalformedResponse,
+    #[error("{0}")]
+    Other(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Sends requests to the vault API, [`ReqwestClient`] by default. `path` is relative to the API
+/// root, e.g. `v1/secret/data/app`.
+#[async_trait]
+pub trait VaultClient {
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        token: &str,
+        body: Option<&str>,
+    ) -> Result<VaultResponse, VaultError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryType {
+    Kv1,
+    Kv2,
+    Db,
+}
+
+impl EntryType {
+    pub fn from_command(command: &str) -> Option<Self> {
+        match command {
+            "kv1" => Some(Self::Kv1),
+            "kv2" => Some(Self::Kv2),
+            "db" => Some(Self::Db),
+            _ => None,
+        }
+    }
+
+    fn api_path(&self, secret_path: &str, version: Option<u64>) -> Result<String, VaultError> {
+        let secret_path = secret_path.trim().trim_matches('/');
+
+        match (self, version) {
+            (Self::Kv1 | Self::Db, None) if !secret_path.is_empty() => {
+                Ok(format!("v1/{secret_path}"))
+            }
+            (Self::Kv2, version) => match secret_path.split_once('/') {
+                Some((mount, path)) if !mount.is_empty() && !path.is_empty() => match version {
+                    Some(version) => Ok(format!("v1/{mount}/data/{path}?version={version}")),
+                    None => Ok(format!("v1/{mount}/data/{path}")),
+                },
+                _ => Err(VaultError::InvalidPath(secret_path.to_owned())),
+            },
+            _ => Err(VaultError::InvalidPath(secret_path.to_owned())),
+        }
+    }
+
+    fn data<'v>(&self, response: &'v Value) -> Option<&'v Value> {
+        match self {
+            Self::Kv1 | Self::Db => response.get("data"),
+            Self::Kv2 => response.select("data.data"),
+        }
+    }
+}
+
+pub async fn fetch_entry(
+    client: &(dyn VaultClient + Send + Sync),
+    token: &str,
+    entry_type: EntryType,
+    secret_path: &str,
+    version: Option<u64>,
+) -> Result<Value, VaultError> {
+    let path = entry_type.api_path(secret_path, version)?;
+    let response = client.send(Method::Get, &path, token, None).await?;
+
+    if response.status != 200 {
+        return Err(VaultError::Status(response.status));
+    }
+
+    let response = response
+        .body
+        .parse::<Value>()
+        .map_err(|_| VaultError::MalformedResponse)?;
+
+    entry_type
+        .data(&response)
+        .cloned()
+        .ok_or(VaultError::MalformedResponse)
+}
+
+/// Splits a trailing `@<version>` qualifier off a field (`password@3`) or, when no field is given,
+/// off the secret path (`secret/app@3`).
+fn split_version(value: &str) -> (&str, Option<u64>) {
+    match value.rsplit_once('@') {
+        Some((rest, version)) => match version.trim().parse() {
+            Ok(version) => (rest.trim_end(), Some(version)),
+            Err(_) => (value, None),
+        },
+        None => (value, None),
+    }
+}
+
+type EntryKey = (EntryType, String, Option<u64>);
+
+pub struct VaultCommand {
+    client: Box<dyn VaultClient + Send + Sync>,
+    token: Token,
+    entries: HashMap<EntryKey, Value>,
+}
+
+impl VaultCommand {
+    pub fn new(client: impl VaultClient + Send + Sync + 'static, token: impl Into<String>) -> Self {
+        Self {
+            client: Box::new(client),
+            token: Token::new(token.into(), Duration::from_secs(300)),
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn with_renew_threshold(mut self, renew_threshold: Duration) -> Self {
+        self.token.set_renew_threshold(renew_threshold);
+        self
+    }
+
+    async fn entry(
+        &mut self,
+        context: &Context,
+        entry_type: EntryType,
+        secret_path: &str,
+        version: Option<u64>,
+    ) -> Result<Value, VaultError> {
+        let key = (entry_type, secret_path.trim().to_owned(), version);
+        if let Some(entry) = self.entries.get(&key) {
+            return Ok(entry.clone());
+        }
+
+        let token = self.token.current(context, self.client.as_ref()).await;
+        let entry = fetch_entry(
+            self.client.as_ref(),
+            token,
+            entry_type,
+            secret_path,
+            version,
+        )
+        .await?;
+
+        self.entries.insert(key, entry.clone());
+
+        Ok(entry)
+    }
+}
+
+#[async_trait]
+impl jakarta::JakartaCommand for VaultCommand {
+    async fn try_process(
+        &mut self,
+        context: &Context,
+        command: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        let (secret_path, field, version) = match &field {
+            Some(field) => {
+                let (field, version) = split_version(field);
+                (args.as_str(), Some(field), version)
+            }
+            None => {
+                let (secret_path, version) = split_version(&args);
+                (secret_path, None, version)
+            }
+        };
+
+        let entry = match EntryType::from_command(&command) {
+            Some(entry_type) => self.entry(context, entry_type, secret_path, version).await,
+            None => Err(VaultError::UnsupportedCommand(command.clone())),
+        }
+        .map_err(|err| CommandError(format!("Could not read secret {args:?}: {err}")))?;
+
+        let field = match field.filter(|field| !field.is_empty()) {
+            Some(field) => field,
+            None => return Ok(entry.to_text()),
+        };
+
+        entry
+            .select(field)
+            .map(Value::to_text)
+            .ok_or_else(|| CommandError(format!("Could not find {field:?} in secret {args:?}")))
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn is_secret(&self) -> bool {
+        true
+    }
+
+    fn end_interpolation(&mut self) {
+        self.entries.clear();
+    }
+
+    fn identifiers(&self) -> Vec<&'static str> {
+        vec!["kv1", "kv2", "db"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::{FallbackCommand, Jakarta, JakartaCommand, RetryCommand};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    struct MockClient {}
+
+    #[async_trait]
+    impl VaultClient for MockClient {
+        async fn send(
+            &self,
+            method: Method,
+            path: &str,
+            token: &str,
+            _: Option<&str>,
+        ) -> Result<VaultResponse, VaultError> {
+            let (status, body) = match (method, path, token) {
+                (_, _, token) if token != "s.token" => (403, r#"{"errors":["permission denied"]}"#),
+                (Method::Get, "v1/auth/token/lookup-self", _) => (200, r#"{"data":{"ttl":0}}"#),
+                (Method::Get, "v1/kv/app", _) => (200, r#"{"data":{"password":"kv1-pass"}}"#),
+                (Method::Get, "v1/secret/data/app", _) => (
+                    200,
+                    r#"{"data":{"data":{"password":"kv2-pass","port":5432},"metadata":{"version":2}}}"#,
+                ),
+                (Method::Get, "v1/secret/data/app?version=1", _) => (
+                    200,
+                    r#"{"data":{"data":{"password":"kv2-old-pass"},"metadata":{"version":1}}}"#,
+                ),
+                (Method::Get, "v1/database/creds/readonly", _) => (
+                    200,
+                    r#"{"lease_id":"database/creds/readonly/1","data":{"username":"v-user","password":"v-pass"}}"#,
+                ),
+                _ => (404, r#"{"errors":[]}"#),
+            };
+
+            Ok(VaultResponse {
+                status,
+                body: body.to_owned(),
+            })
+        }
+    }
+
+    struct FlakyClient {
+        failures: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl VaultClient for FlakyClient {
+        async fn send(
+            &self,
+            method: Method,
+            path: &str,
+            token: &str,
+            body: Option<&str>,
+        ) -> Result<VaultResponse, VaultError> {
+            let failed = self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |failures| {
+                    failures.checked_sub(1)
+                })
+                .is_ok();
+
+            match failed {
+                true => Err(VaultError::Other("connection reset".to_owned())),
+                false => MockClient {}.send(method, path, token, body).await,
+            }
+        }
+    }
+
+    #[test]
+    fn it_builds_api_paths() {
+        assert_eq!(
+            EntryType::Kv1.api_path("/kv/app", None).unwrap(),
+            "v1/kv/app".to_owned()
+        );
+        assert_eq!(
+            EntryType::Kv2.api_path("secret/team/app", None).unwrap(),
+            "v1/secret/data/team/app".to_owned()
+        );
+        assert_eq!(
+            EntryType::Kv2.api_path("secret/team/app", Some(3)).unwrap(),
+            "v1/secret/data/team/app?version=3".to_owned()
+        );
+        assert_eq!(
+            EntryType::Db
+                .api_path("database/creds/readonly", None)
+                .unwrap(),
+            "v1/database/creds/readonly".to_owned()
+        );
+        assert!(EntryType::Kv2.api_path("secret", None).is_err());
+        assert!(EntryType::Kv1.api_path(" ", None).is_err());
+        assert!(EntryType::Kv1.api_path("kv/app", Some(1)).is_err());
+    }
+
+    #[test]
+    fn it_splits_version_qualifiers() {
+        assert_eq!(split_version("password@3"), ("password", Some(3)));
+        assert_eq!(split_version("secret/app @ 12"), ("secret/app", Some(12)));
+        assert_eq!(split_version("password"), ("password", None));
+        assert_eq!(
+            split_version("user@example.com"),
+            ("user@example.com", None)
+        );
+        assert_eq!(split_version("@3"), ("", Some(3)));
+    }
+
+    #[tokio::test]
+    async fn it_retries_transient_failures() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let flaky_client = |failures| FlakyClient {
+            failures: AtomicUsize::new(failures),
+        };
+        commands.insert(
+            "kv2",
+            Arc::new(Mutex::new(RetryCommand::new(
+                VaultCommand::new(flaky_client(2), "s.token"),
+                2,
+                Duration::ZERO,
+            ))),
+        );
+        commands.insert(
+            "kv1",
+            Arc::new(Mutex::new(RetryCommand::new(
+                VaultCommand::new(flaky_client(usize::MAX), "s.token"),
+                1,
+                Duration::ZERO,
+            ))),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${kv2:secret/app#password} ${kv1:kv/app#password:-gave_up}".to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "kv2-pass gave_up".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_dispatches_on_the_command() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let vault_cmd = Arc::new(Mutex::new(VaultCommand::new(MockClient {}, "s.token")));
+        commands.insert("kv1", vault_cmd.clone());
+        commands.insert("kv2", vault_cmd.clone());
+        commands.insert("db", vault_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${kv1:kv/app#password} ${kv2:secret/app#password} ${kv2:secret/app#port} ${db:database/creds/readonly#username}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "kv1-pass kv2-pass 5432 v-user".to_owned());
+
+        let result = jakarta
+            .interpolate_string("${kv2:secret/app}".to_owned())
+            .await;
+
+        assert_eq!(result, r#"{"password":"kv2-pass","port":5432}"#.to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_reads_pinned_kv2_versions() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let vault_cmd = Arc::new(Mutex::new(VaultCommand::new(MockClient {}, "s.token")));
+        commands.insert("kv2", vault_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${kv2:secret/app#password@1} ${kv2:secret/app#password} ${kv2:secret/app@1}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            r#"kv2-old-pass kv2-pass {"password":"kv2-old-pass"}"#.to_owned()
+        );
+
+        let result = jakarta
+            .interpolate_string("${kv2:secret/app#password@7:-default_value}".to_owned())
+            .await;
+
+        assert_eq!(result, "default_value".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_default_on_errors() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let vault_cmd = Arc::new(Mutex::new(VaultCommand::new(MockClient {}, "s.token")));
+        let unauthorized_cmd = Arc::new(Mutex::new(VaultCommand::new(MockClient {}, "s.wrong")));
+        commands.insert("kv1", vault_cmd.clone());
+        commands.insert("kv2", vault_cmd.clone());
+        commands.insert("vault", vault_cmd.clone());
+        commands.insert("unauthorized", unauthorized_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        for template in [
+            "${kv2:secret/missing#password:-default_value}",
+            "${kv2:secret/app#username:-default_value}",
+            "${kv1:kv:-default_value}",
+            "${vault:kv/app#password:-default_value}",
+            "${unauthorized:kv/app#password:-default_value}",
+        ] {
+            let result = jakarta.interpolate_string(template.to_owned()).await;
+
+            assert_eq!(result, "default_value".to_owned());
+        }
+    }
+
+    #[derive(Default)]
+    struct ExpiringClient {
+        requests: std::sync::Mutex<Vec<(Method, String, String)>>,
+    }
+
+    #[async_trait]
+    impl VaultClient for Arc<ExpiringClient> {
+        async fn send(
+            &self,
+            method: Method,
+            path: &str,
+            token: &str,
+            _: Option<&str>,
+        ) -> Result<VaultResponse, VaultError> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((method, path.to_owned(), token.to_owned()));
+
+            let (status, body) = match (method, path, token) {
+                (Method::Get, "v1/auth/token/lookup-self", "s.expiring") => {
+                    (200, r#"{"data":{"ttl":30}}"#)
+                }
+                (Method::Post, "v1/auth/token/renew-self", "s.expiring") => (
+                    200,
+                    r#"{"auth":{"client_token":"s.renewed","lease_duration":3600,"renewable":true}}"#,
+                ),
+                (Method::Get, "v1/kv/app", "s.renewed") => {
+                    (200, r#"{"data":{"password":"kv1-pass"}}"#)
+                }
+                _ => (403, r#"{"errors":["permission denied"]}"#),
+            };
+
+            Ok(VaultResponse {
+                status,
+                body: body.to_owned(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn it_renews_expiring_tokens() {
+        let client = Arc::new(ExpiringClient::default());
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let vault_cmd = Arc::new(Mutex::new(
+            VaultCommand::new(client.clone(), "s.expiring")
+                .with_renew_threshold(Duration::from_secs(60)),
+        ));
+        commands.insert("kv1", vault_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        for _ in 0..2 {
+            let result = jakarta
+                .interpolate_string("${kv1:kv/app#password}".to_owned())
+                .await;
+
+            assert_eq!(result, "kv1-pass".to_owned());
+        }
+
+        assert_eq!(
+            *client.requests.lock().unwrap(),
+            vec![
+                (
+                    Method::Get,
+                    "v1/auth/token/lookup-self".to_owned(),
+                    "s.expiring".to_owned()
+                ),
+                (
+                    Method::Post,
+                    "v1/auth/token/renew-self".to_owned(),
+                    "s.expiring".to_owned()
+                ),
+                (Method::Get, "v1/kv/app".to_owned(), "s.renewed".to_owned()),
+                (Method::Get, "v1/kv/app".to_owned(), "s.renewed".to_owned()),
+            ]
+        );
+    }
+
+    #[derive(Default)]
+    struct CountingClient {
+        reads: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl VaultClient for Arc<CountingClient> {
+        async fn send(
+            &self,
+            method: Method,
+            path: &str,
+            token: &str,
+            body: Option<&str>,
+        ) -> Result<VaultResponse, VaultError> {
+            if path.starts_with("v1/auth/") {
+                return MockClient {}.send(method, path, token, body).await;
+            }
+
+            let reads = self.reads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+            Ok(VaultResponse {
+                status: 200,
+                body: format!(
+                    r#"{{"data":{{"username":"v-user-{reads}","password":"v-pass-{reads}"}}}}"#
+                ),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn it_resolves_through_fallbacks() {
+        let client = Arc::new(CountingClient::default());
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let vault_cmd = Arc::new(Mutex::new(VaultCommand::new(client.clone(), "s.token")));
+        commands.insert(
+            "secret",
+            Arc::new(Mutex::new(FallbackCommand::new(vec![("db", vault_cmd)]))),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${secret:database/creds/readonly#username}:${secret:database/creds/readonly#password}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "v-user-0:v-pass-0".to_owned());
+
+        let result = jakarta
+            .interpolate_string("${secret:database/creds/readonly#username}".to_owned())
+            .await;
+
+        assert_eq!(result, "v-user-1".to_owned());
+        assert_eq!(client.reads.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn it_reads_each_path_once_per_interpolation() {
+        let client = Arc::new(CountingClient::default());
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let vault_cmd = Arc::new(Mutex::new(VaultCommand::new(client.clone(), "s.token")));
+        commands.insert("db", vault_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${db:database/creds/readonly#username}:${db:database/creds/readonly#password}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "v-user-0:v-pass-0".to_owned());
+        assert_eq!(client.reads.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let result = jakarta
+            .interpolate_string("${db:database/creds/readonly#username}".to_owned())
+            .await;
+
+        assert_eq!(result, "v-user-1".to_owned());
+        assert_eq!(client.reads.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+}
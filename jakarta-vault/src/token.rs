@@ -0,0 +1,208 @@
+use std::time::{Duration, Instant};
+
+use jakarta::Context;
+use jakarta_json::{Value, ValueExt};
+
+use crate::{Method, VaultClient, VaultError};
+
+/// How long to keep using a token without knowing its ttl after a failed lookup, before looking
+/// it up again.
+const LOOKUP_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expiry {
+    Unknown,
+    Never,
+    At(Instant),
+}
+
+pub(crate) struct Token {
+    value: String,
+    expiry: Expiry,
+    renew_threshold: Duration,
+    next_lookup: Option<Instant>,
+}
+
+impl Token {
+    pub fn new(value: String, renew_threshold: Duration) -> Self {
+        Self {
+            value,
+            expiry: Expiry::Unknown,
+            renew_threshold,
+            next_lookup: None,
+        }
+    }
+
+    pub fn set_renew_threshold(&mut self, renew_threshold: Duration) {
+        self.renew_threshold = renew_threshold;
+    }
+
+    /// Returns the token to use for the next request, looking up its ttl on first use and
+    /// renewing it once the remaining ttl drops below the renew threshold. A failed lookup is
+    /// retried once the lookup backoff has passed.
+    pub async fn current(
+        &mut self,
+        context: &Context,
+        client: &(dyn VaultClient + Send + Sync),
+    ) -> &str {
+        let lookup_due = self
+            .next_lookup
+            .is_none_or(|next_lookup| Instant::now() >= next_lookup);
+
+        if self.expiry == Expiry::Unknown && lookup_due {
+            match self.lookup(client).await {
+                Ok(expiry) => {
+                    self.expiry = expiry;
+                    self.next_lookup = None;
+                }
+                Err(err) => {
+                    context.diagnostics().warn(&format!(
+                        "Could not look up vault token: {err}, retrying in {LOOKUP_BACKOFF:?}"
+                    ));
+
+                    self.next_lookup = Some(Instant::now() + LOOKUP_BACKOFF);
+                }
+            }
+        }
+
+        if let Expiry::At(expires_at) = self.expiry {
+            if expires_at.saturating_duration_since(Instant::now()) <= self.renew_threshold {
+                if let Err(err) = self.renew(client).await {
+                    context
+                        .diagnostics()
+                        .warn(&format!("Could not renew vault token: {err}"));
+                }
+            }
+        }
+
+        &self.value
+    }
+
+    async fn lookup(&self, client: &(dyn VaultClient + Send + Sync)) -> Result<Expiry, VaultError> {
+        let response = send(
+            client,
+            Method::Get,
+            "v1/auth/token/lookup-self",
+            &self.value,
+        )
+        .await?;
+
+        let ttl = response
+            .select("data.ttl")
+            .and_then(seconds)
+            .ok_or(VaultError::MalformedResponse)?;
+
+        Ok(expiry(ttl))
+    }
+
+    async fn renew(&mut self, client: &(dyn VaultClient + Send + Sync)) -> Result<(), VaultError> {
+        let response = send(
+            client,
+            Method::Post,
+            "v1/auth/token/renew-self",
+            &self.value,
+        )
+        .await?;
+
+        let lease_duration = response
+            .select("auth.lease_duration")
+            .and_then(seconds)
+            .ok_or(VaultError::MalformedResponse)?;
+
+        if let Some(Value::String(client_token)) = response.select("auth.client_token") {
+            self.value = client_token.clone();
+        }
+        self.expiry = expiry(lease_duration);
+
+        Ok(())
+    }
+}
+
+async fn send(
+    client: &(dyn VaultClient + Send + Sync),
+    method: Method,
+    path: &str,
+    token: &str,
+) -> Result<Value, VaultError> {
+    let response = client.send(method, path, token, None).await?;
+
+    if response.status != 200 {
+        return Err(VaultError::Status(response.status));
+    }
+
+    response
+        .body
+        .parse()
+        .map_err(|_| VaultError::MalformedResponse)
+}
+
+fn seconds(value: &Value) -> Option<u64> {
+    value.as_u64()
+}
+
+fn expiry(ttl: u64) -> Expiry {
+    match ttl {
+        0 => Expiry::Never,
+        ttl => Expiry::At(Instant::now() + Duration::from_secs(ttl)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::VaultResponse;
+
+    /// Fails the first lookup, then reports a ttl of an hour.
+    #[derive(Default)]
+    struct FlakyClient {
+        lookups: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl VaultClient for FlakyClient {
+        async fn send(
+            &self,
+            _: Method,
+            _: &str,
+            _: &str,
+            _: Option<&str>,
+        ) -> Result<VaultResponse, VaultError> {
+            let mut lookups = self.lookups.lock().unwrap();
+            *lookups += 1;
+
+            Ok(match *lookups {
+                1 => VaultResponse {
+                    status: 503,
+                    body: "".to_owned(),
+                },
+                _ => VaultResponse {
+                    status: 200,
+                    body: r#"{"data":{"ttl":3600}}"#.to_owned(),
+                },
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn it_retries_failed_lookups_after_the_backoff() {
+        let context = Context::new();
+        let client = FlakyClient::default();
+        let mut token = Token::new("s.token".to_owned(), Duration::from_secs(60));
+
+        assert_eq!(token.current(&context, &client).await, "s.token");
+        assert_eq!(token.expiry, Expiry::Unknown);
+
+        token.current(&context, &client).await;
+        assert_eq!(*client.lookups.lock().unwrap(), 1);
+
+        token.next_lookup = Some(Instant::now());
+        token.current(&context, &client).await;
+        assert_eq!(*client.lookups.lock().unwrap(), 2);
+        assert!(matches!(token.expiry, Expiry::At(_)));
+        assert_eq!(token.next_lookup, None);
+    }
+}
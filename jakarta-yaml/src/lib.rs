@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+use jakarta::{Jakarta, JakartaError};
+use serde_yaml::Value;
+
+/// Interpolates the string scalars of a YAML document in place and re-serializes it, so resolved
+/// values containing `: `, ` #` or newlines are quoted as needed and can't change the document's
+/// structure. Keys are left as is. Comments and the original formatting aren't preserved.
+#[async_trait]
+pub trait InterpolateYaml {
+    async fn interpolate_yaml(&self, yaml: &str) -> Result<String, JakartaError>;
+}
+
+#[async_trait]
+impl InterpolateYaml for Jakarta<'_> {
+    async fn interpolate_yaml(&self, yaml: &str) -> Result<String, JakartaError> {
+        let value = serde_yaml::from_str::<Value>(yaml).map_err(error)?;
+        let value = interpolate_value(self, value).await?;
+
+        serde_yaml::to_string(&value).map_err(error)
+    }
+}
+
+async fn interpolate_value(jakarta: &Jakarta<'_>, value: Value) -> Result<Value, JakartaError> {
+    Ok(match value {
+        Value::String(value) => Value::String(jakarta.try_interpolate_string(value).await?),
+        Value::Sequence(values) => {
+            let mut interpolated_values = Vec::with_capacity(values.len());
+            for value in values {
+                interpolated_values.push(Box::pin(interpolate_value(jakarta, value)).await?);
+            }
+
+            Value::Sequence(interpolated_values)
+        }
+        Value::Mapping(entries) => {
+            let mut interpolated_entries = serde_yaml::Mapping::with_capacity(entries.len());
+            for (key, value) in entries {
+                let value = Box::pin(interpolate_value(jakarta, value)).await?;
+                interpolated_entries.insert(key, value);
+            }
+
+            Value::Mapping(interpolated_entries)
+        }
+        Value::Tagged(mut tagged) => {
+            tagged.value = Box::pin(interpolate_value(jakarta, tagged.value)).await?;
+
+            Value::Tagged(tagged)
+        }
+        value => value,
+    })
+}
+
+fn error(err: serde_yaml::Error) -> JakartaError {
+    let idx = err.location().map_or(0, |location| location.index());
+
+    JakartaError::ParseError {
+        message: err.to_string(),
+        span: idx..idx,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jakarta::JakartaCommand;
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    struct LookupCommand {}
+
+    #[async_trait]
+    impl JakartaCommand for LookupCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> String {
+            match args.as_str() {
+                "URL" => "postgres://host:5432/db".to_owned(),
+                "MAPPING" => "a: b # not a comment".to_owned(),
+                "FLOW" => "x, y]: {z".to_owned(),
+                "QUOTE" => "it's \"quoted\"".to_owned(),
+                "LINES" => "line 1\nline 2".to_owned(),
+                "PORT" => "5432".to_owned(),
+                args => args.to_owned(),
+            }
+        }
+    }
+
+    fn jakarta() -> Jakarta<'static> {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("env", Arc::new(Mutex::new(LookupCommand {})));
+
+        Jakarta::new(commands).unwrap()
+    }
+
+    fn parse(yaml: &str) -> Value {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_quotes_values_with_special_characters() {
+        let yaml = "\
+database:
+  url: ${env:URL}
+  mapping: ${env:MAPPING} # comment
+  port: 5432
+  quoted: '${env:QUOTE}'
+  double: \"${env:QUOTE}\"
+hosts:
+  - ${env:MAPPING}
+  - name: ${env:LINES}
+";
+
+        let result = jakarta().interpolate_yaml(yaml).await.unwrap();
+
+        assert_eq!(
+            parse(&result),
+            parse(
+                "\
+database:
+  url: 'postgres://host:5432/db'
+  mapping: 'a: b # not a comment'
+  port: 5432
+  quoted: 'it''s \"quoted\"'
+  double: 'it''s \"quoted\"'
+hosts:
+  - 'a: b # not a comment'
+  - name: \"line 1\\nline 2\"
+"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_scalars_in_flow_collections() {
+        let yaml = "\
+script: |
+  echo ${env:LINES}
+tags: [a, '${env:FLOW}', 1]
+env: {url: '${env:URL}', '${env:key}': '${env:plain}'}
+";
+
+        let result = jakarta().interpolate_yaml(yaml).await.unwrap();
+
+        assert_eq!(
+            parse(&result),
+            parse(
+                "\
+script: \"echo line 1\\nline 2\\n\"
+tags: [a, 'x, y]: {z', 1]
+env: {url: 'postgres://host:5432/db', '${env:key}': plain}
+"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_malformed_documents() {
+        let result = jakarta().interpolate_yaml("a: [1, 2\n").await;
+
+        assert!(matches!(result, Err(JakartaError::ParseError { .. })));
+    }
+}
@@ -0,0 +1,59 @@
+//! Compares the hand-written placeholder parser against the regex the crate used before it.
+
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use jakarta::Jakarta;
+use regex::{Captures, Regex};
+
+const LEGACY_PATTERN: &str = r"\$(?P<exclude>\$){0,1}\{(?:\s*(?P<command>[^:]+)\s*:\s*(?P<args>[^{}]+?)\s*(?:(?::-)(?P<default_value>.+)){0,1}\s*?){0,1}}";
+
+fn template() -> String {
+    (0..100)
+        .map(|idx| {
+            format!("key_{idx}=${{env:VAR_{idx}:-default_{idx}}} path=${{file:/etc/app/{idx}.conf}} $${{env:SKIPPED}}\n")
+        })
+        .collect()
+}
+
+fn construction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("construct");
+
+    group.bench_function("legacy_regex", |b| {
+        b.iter(|| Regex::new(black_box(LEGACY_PATTERN)).unwrap())
+    });
+    group.bench_function("parser", |b| {
+        b.iter(|| Jakarta::new(black_box(HashMap::new())).unwrap())
+    });
+
+    group.finish();
+}
+
+fn scanning(c: &mut Criterion) {
+    let template = template();
+    let mut group = c.benchmark_group("scan");
+
+    let regex = Regex::new(LEGACY_PATTERN).unwrap();
+    group.bench_function("legacy_regex", |b| {
+        b.iter(|| {
+            regex.replace_all(black_box(&template), |captures: &Captures| {
+                match (captures.name("command"), captures.name("args")) {
+                    (Some(command), Some(args)) => {
+                        format!("<{}:{}>", command.as_str(), args.as_str())
+                    }
+                    _ => captures[0].to_owned(),
+                }
+            })
+        })
+    });
+
+    let jakarta = Jakarta::new(HashMap::new()).unwrap();
+    group.bench_function("parser", |b| {
+        b.iter(|| jakarta.preview(black_box(&template)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, construction, scanning);
+criterion_main!(benches);
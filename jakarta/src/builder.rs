@@ -0,0 +1,233 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::sync::Mutex;
+
+use crate::{
+    commands::JakartaCommand,
+    diagnostics::{Diagnostics, TracingDiagnostics},
+    filters::FilterRegistry,
+    jakarta::{
+        Jakarta, JakartaError, OnMissing, DEFAULT_CLOSING_DELIMITER, DEFAULT_OPENING_DELIMITER,
+    },
+    metrics::MetricsRecorder,
+    parser::Parser,
+};
+
+pub struct JakartaBuilder<'a> {
+    command_map: HashMap<&'a str, Arc<Mutex<dyn JakartaCommand>>>,
+    filters: FilterRegistry,
+    opening_delimiter: String,
+    closing_delimiter: String,
+    max_output_len: Option<usize>,
+    max_iterations: Option<usize>,
+    metrics: Option<Arc<dyn MetricsRecorder>>,
+    diagnostics: Arc<dyn Diagnostics>,
+    default_command: Option<Arc<Mutex<dyn JakartaCommand>>>,
+    case_insensitive_commands: bool,
+    on_missing: OnMissing,
+}
+
+impl<'a> JakartaBuilder<'a> {
+    pub fn new() -> Self {
+        Self {
+            command_map: HashMap::new(),
+            filters: FilterRegistry::default(),
+            opening_delimiter: DEFAULT_OPENING_DELIMITER.to_owned(),
+            closing_delimiter: DEFAULT_CLOSING_DELIMITER.to_owned(),
+            max_output_len: None,
+            max_iterations: None,
+            metrics: None,
+            diagnostics: Arc::new(TracingDiagnostics),
+            default_command: None,
+            case_insensitive_commands: false,
+            on_missing: OnMissing::default(),
+        }
+    }
+
+    pub fn command(mut self, id: &'a str, command: Arc<Mutex<dyn JakartaCommand>>) -> Self {
+        self.command_map.insert(id, command);
+        self
+    }
+
+    /// Registers `command` under every id returned by [`JakartaCommand::identifiers`].
+    pub fn register_auto(mut self, command: impl JakartaCommand + 'static) -> Self {
+        let identifiers = command.identifiers();
+        let command: Arc<Mutex<dyn JakartaCommand>> = Arc::new(Mutex::new(command));
+
+        for id in identifiers {
+            self.command_map.insert(id, command.clone());
+        }
+        self
+    }
+
+    /// Handles placeholders whose id matches no registered command, receiving that id as its
+    /// `command` instead of them resolving to an empty string.
+    pub fn default_command(mut self, command: Arc<Mutex<dyn JakartaCommand>>) -> Self {
+        self.default_command = Some(command);
+        self
+    }
+
+    /// Matches `${ENV:FOO}` and `${Env:FOO}` against a command registered as `env`. Off by default,
+    /// as ids differing only in case would otherwise collide; `build` fails with
+    /// [`JakartaError::AmbiguousCommands`] if any do.
+    pub fn case_insensitive_commands(mut self, case_insensitive_commands: bool) -> Self {
+        self.case_insensitive_commands = case_insensitive_commands;
+        self
+    }
+
+    pub fn on_missing(mut self, on_missing: OnMissing) -> Self {
+        self.on_missing = on_missing;
+        self
+    }
+
+    pub fn commands(
+        mut self,
+        command_map: HashMap<&'a str, Arc<Mutex<dyn JakartaCommand>>>,
+    ) -> Self {
+        self.command_map.extend(command_map);
+        self
+    }
+
+    pub fn filter(
+        mut self,
+        name: impl Into<String>,
+        filter: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.filters.register(name, filter);
+        self
+    }
+
+    pub fn parameterized_filter(
+        mut self,
+        name: impl Into<String>,
+        filter: impl Fn(&str, &[String]) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.filters.register_parameterized(name, filter);
+        self
+    }
+
+    pub fn delimiters(mut self, opening: impl Into<String>, closing: impl Into<String>) -> Self {
+        self.opening_delimiter = opening.into();
+        self.closing_delimiter = closing.into();
+        self
+    }
+
+    /// Aborts interpolation once the working string grows past `max_output_len` bytes, which
+    /// `try_interpolate_string` reports as [`JakartaError::OutputTooLarge`].
+    pub fn max_output_len(mut self, max_output_len: usize) -> Self {
+        self.max_output_len = Some(max_output_len);
+        self
+    }
+
+    /// Stops re-expanding resolved values after `max_iterations` passes, e.g. when a command
+    /// keeps returning placeholders. `try_interpolate_string` reports this as
+    /// [`JakartaError::MaxIterationsExceeded`].
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = Some(max_iterations);
+        self
+    }
+
+    pub fn metrics_recorder(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.metrics = Some(recorder);
+        self
+    }
+
+    /// Reports warnings and errors to `diagnostics` instead of `tracing`. Commands receive it
+    /// through [`crate::Context::diagnostics`].
+    pub fn diagnostics(mut self, diagnostics: Arc<dyn Diagnostics>) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
+    pub fn build(self) -> Result<Jakarta<'a>, JakartaError> {
+        if self.case_insensitive_commands {
+            let mut ids = self.command_map.keys().collect::<Vec<_>>();
+            ids.sort_by_key(|id| id.to_ascii_lowercase());
+
+            if let Some(pair) = ids
+                .windows(2)
+                .find(|pair| pair[0].eq_ignore_ascii_case(pair[1]))
+            {
+                return Err(JakartaError::AmbiguousCommands(
+                    pair[0].to_string(),
+                    pair[1].to_string(),
+                ));
+            }
+        }
+
+        Ok(Jakarta {
+            parser: Parser::new(&self.opening_delimiter, &self.closing_delimiter)?,
+            command_map: self.command_map,
+            filters: self.filters,
+            max_output_len: self.max_output_len,
+            max_iterations: self.max_iterations,
+            metrics: self.metrics,
+            diagnostics: self.diagnostics,
+            default_command: self.default_command,
+            case_insensitive_commands: self.case_insensitive_commands,
+            on_missing: self.on_missing,
+        })
+    }
+}
+
+impl<'a> Default for JakartaBuilder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use super::*;
+
+    struct MultiCommand {}
+
+    #[async_trait]
+    impl JakartaCommand for MultiCommand {
+        async fn process(
+            &mut self,
+            command: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> String {
+            format!("{command}={args}")
+        }
+
+        fn identifiers(&self) -> Vec<&'static str> {
+            vec!["kv1", "kv2", "db"]
+        }
+    }
+
+    #[tokio::test]
+    async fn it_dispatches_unknown_ids_to_the_default_command() {
+        let jakarta = JakartaBuilder::new()
+            .register_auto(MultiCommand {})
+            .default_command(Arc::new(Mutex::new(MultiCommand {})))
+            .build()
+            .unwrap();
+
+        let result = jakarta
+            .interpolate_string("${kv1:a} ${anything:x} ${plugin.scope:y}".to_owned())
+            .await;
+
+        assert_eq!(result, "kv1=a anything=x plugin.scope=y".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_registers_commands_under_all_identifiers() {
+        let jakarta = JakartaBuilder::new()
+            .register_auto(MultiCommand {})
+            .build()
+            .unwrap();
+
+        let result = jakarta
+            .interpolate_string("${kv1:a} ${kv2:b} ${db:c} ${other:d}".to_owned())
+            .await;
+
+        assert_eq!(result, "kv1=a kv2=b db=c ".to_owned());
+    }
+}
@@ -0,0 +1,274 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    commands::{CommandError, JakartaCommand},
+    context::Context,
+};
+
+/// Returns the current time, injectable so tests can advance time manually.
+pub type Clock = Arc<dyn Fn() -> Instant + Send + Sync>;
+
+type CacheKey = (String, Option<String>, String, Option<String>);
+
+/// Caches `inner`'s values across `interpolate_string` calls, re-fetching a value once it's
+/// older than `ttl`. Failed fetches, empty values and default values aren't cached, and the
+/// default value is passed on to `inner`.
+pub struct CacheLayer<C> {
+    inner: C,
+    ttl: Duration,
+    clock: Clock,
+    entries: HashMap<CacheKey, (String, Instant)>,
+}
+
+impl<C: JakartaCommand> CacheLayer<C> {
+    pub fn new(inner: C, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            clock: Arc::new(Instant::now),
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Drops all cached values, so the next lookup of each re-fetches it.
+    pub fn invalidate(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[async_trait]
+impl<C: JakartaCommand> JakartaCommand for CacheLayer<C> {
+    async fn process_with_context(
+        &mut self,
+        context: &Context,
+        command: String,
+        instance: Option<String>,
+        args: String,
+        field: Option<String>,
+        default_value: Option<String>,
+    ) -> String {
+        match self
+            .try_process(
+                context,
+                command,
+                instance,
+                args.clone(),
+                field,
+                default_value.clone(),
+            )
+            .await
+        {
+            Ok(value) if value.is_empty() => default_value.unwrap_or(value),
+            Ok(value) => value,
+            Err(err) => {
+                context.diagnostics().warn(&format!(
+                    "Failed to fetch {args:?}: {err}, resolving to default value"
+                ));
+
+                default_value.unwrap_or_else(|| "".to_owned())
+            }
+        }
+    }
+
+    async fn try_process(
+        &mut self,
+        context: &Context,
+        command: String,
+        instance: Option<String>,
+        args: String,
+        field: Option<String>,
+        default_value: Option<String>,
+    ) -> Result<String, CommandError> {
+        let key = (command, instance, args, field);
+        let now = (self.clock)();
+
+        if let Some((value, fetched_at)) = self.entries.get(&key) {
+            if now.saturating_duration_since(*fetched_at) < self.ttl {
+                return Ok(value.clone());
+            }
+        }
+
+        let (command, instance, args, field) = key.clone();
+        let value = self
+            .inner
+            .try_process(
+                context,
+                command,
+                instance,
+                args,
+                field,
+                default_value.clone(),
+            )
+            .await?;
+
+        // A command without `try_process` resolves failures to the default value it was handed.
+        if !value.is_empty() && default_value.as_ref() != Some(&value) {
+            self.entries.insert(key, (value.clone(), now));
+        }
+
+        Ok(value)
+    }
+
+    fn cacheable(&self) -> bool {
+        self.inner.cacheable()
+    }
+
+    fn expand_result(&self) -> bool {
+        self.inner.expand_result()
+    }
+
+    fn is_secret(&self) -> bool {
+        self.inner.is_secret()
+    }
+
+    fn marks_nested_values(&self) -> bool {
+        self.inner.marks_nested_values()
+    }
+
+    fn end_interpolation(&mut self) {
+        self.inner.end_interpolation()
+    }
+
+    fn identifiers(&self) -> Vec<&'static str> {
+        self.inner.identifiers()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::Jakarta;
+
+    struct CountingCommand {
+        fetches: usize,
+    }
+
+    #[async_trait]
+    impl JakartaCommand for CountingCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> String {
+            self.fetches += 1;
+
+            format!("{args}@{}", self.fetches)
+        }
+    }
+
+    #[tokio::test]
+    async fn it_refreshes_values_after_the_ttl() {
+        let start = Instant::now();
+        let elapsed = Arc::new(std::sync::Mutex::new(Duration::ZERO));
+        let clock_elapsed = elapsed.clone();
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert(
+            "vault",
+            Arc::new(Mutex::new(
+                CacheLayer::new(CountingCommand { fetches: 0 }, Duration::from_secs(60))
+                    .with_clock(Arc::new(move || start + *clock_elapsed.lock().unwrap())),
+            )),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let template = "${vault:a} ${vault:b}".to_owned();
+
+        assert_eq!(
+            jakarta.interpolate_string(template.clone()).await,
+            "a@1 b@2"
+        );
+
+        *elapsed.lock().unwrap() = Duration::from_secs(59);
+        assert_eq!(
+            jakarta.interpolate_string(template.clone()).await,
+            "a@1 b@2"
+        );
+
+        *elapsed.lock().unwrap() = Duration::from_secs(60);
+        assert_eq!(
+            jakarta.interpolate_string(template.clone()).await,
+            "a@3 b@4"
+        );
+
+        *elapsed.lock().unwrap() = Duration::from_secs(90);
+        assert_eq!(jakarta.interpolate_string(template).await, "a@3 b@4");
+    }
+
+    struct LookupCommand {
+        fetches: usize,
+        values: HashMap<&'static str, &'static str>,
+    }
+
+    #[async_trait]
+    impl JakartaCommand for LookupCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            default_value: Option<String>,
+        ) -> String {
+            self.fetches += 1;
+
+            match self.values.get(args.as_str()) {
+                Some(value) => value.to_string(),
+                None => default_value.unwrap_or_default(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn it_does_not_cache_empty_or_default_values() {
+        let lookup_cmd = Arc::new(Mutex::new(CacheLayer::new(
+            LookupCommand {
+                fetches: 0,
+                values: HashMap::from([("A", "a"), ("EMPTY", "")]),
+            },
+            Duration::from_secs(60),
+        )));
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("env", lookup_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let template = "[${env:A}] [${env:MISSING:=x}] [${env:EMPTY:-y}] [${env:EMPTY}]".to_owned();
+        assert_eq!(
+            jakarta.interpolate_string(template.clone()).await,
+            "[a] [x] [y] []"
+        );
+        assert_eq!(jakarta.interpolate_string(template).await, "[a] [x] [y] []");
+        assert_eq!(lookup_cmd.lock().await.inner.fetches, 7);
+
+        let value = lookup_cmd
+            .lock()
+            .await
+            .process(
+                "env".to_owned(),
+                None,
+                "EMPTY".to_owned(),
+                None,
+                Some("z".to_owned()),
+            )
+            .await;
+        assert_eq!(value, "z");
+    }
+}
@@ -0,0 +1,123 @@
+use std::future::Future;
+
+use async_trait::async_trait;
+
+use crate::commands::JakartaCommand;
+
+/// Adapts an async closure taking `(command, args, field, default_value)` into a command, e.g.
+/// `FnCommand::new(|_, args, _, _| async move { args.to_uppercase() })`.
+pub struct FnCommand<F> {
+    f: F,
+}
+
+impl<F, Fut> FnCommand<F>
+where
+    F: Fn(String, String, Option<String>, Option<String>) -> Fut + Send,
+    Fut: Future<Output = String> + Send,
+{
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+#[async_trait]
+impl<F, Fut> JakartaCommand for FnCommand<F>
+where
+    F: Fn(String, String, Option<String>, Option<String>) -> Fut + Send,
+    Fut: Future<Output = String> + Send,
+{
+    async fn process(
+        &mut self,
+        command: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        default_value: Option<String>,
+    ) -> String {
+        (self.f)(command, args, field, default_value).await
+    }
+}
+
+/// Like [`FnCommand`], but for pure synchronous closures, e.g.
+/// `SyncFnCommand::new(|_, args, _, _| args.to_uppercase())`.
+pub struct SyncFnCommand<F> {
+    f: F,
+}
+
+impl<F> SyncFnCommand<F>
+where
+    F: Fn(String, String, Option<String>, Option<String>) -> String + Send,
+{
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+#[async_trait]
+impl<F> JakartaCommand for SyncFnCommand<F>
+where
+    F: Fn(String, String, Option<String>, Option<String>) -> String + Send,
+{
+    async fn process(
+        &mut self,
+        command: String,
+        _: Option<String>,
+        args: String,
+        field: Option<String>,
+        default_value: Option<String>,
+    ) -> String {
+        (self.f)(command, args, field, default_value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::Jakarta;
+
+    #[tokio::test]
+    async fn it_interpolates_with_closures() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert(
+            "greet",
+            Arc::new(Mutex::new(FnCommand::new(
+                |command, args, field, default_value| async move {
+                    match (args.as_str(), field) {
+                        ("", _) => default_value.unwrap_or_default(),
+                        (args, Some(field)) => format!("{command}: {field}, {args}"),
+                        (args, None) => format!("{command}: hello, {args}"),
+                    }
+                },
+            ))),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${greet:world} ${greet:you#hi} ${greet:?a:b}".to_owned())
+            .await;
+
+        assert_eq!(result, "greet: hello, world greet: hi, you b".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_with_sync_closures() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert(
+            "upper",
+            Arc::new(Mutex::new(SyncFnCommand::new(|_, args, _, _| {
+                args.to_uppercase()
+            }))),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${upper:hello} ${upper:${upper:nested}}".to_owned())
+            .await;
+
+        assert_eq!(result, "HELLO NESTED".to_owned());
+    }
+}
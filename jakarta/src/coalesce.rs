@@ -0,0 +1,213 @@
+use async_trait::async_trait;
+
+use crate::{
+    commands::{CommandError, JakartaCommand},
+    context::Context,
+    jakarta::{PROTECTED_VALUE_END, PROTECTED_VALUE_START},
+};
+
+const DEFAULT_DELIMITER: char = ':';
+
+/// Resolves `${coalesce:${env:A}:${env:B}:literal}` to the first non-empty argument, after nested
+/// placeholders were resolved. Only delimiters written in the template split the args, so resolved
+/// values may contain them. Literal delimiters are escaped as `\:`.
+pub struct CoalesceCommand {
+    delimiter: char,
+}
+
+impl CoalesceCommand {
+    pub fn new() -> Self {
+        Self {
+            delimiter: DEFAULT_DELIMITER,
+        }
+    }
+
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Splits `args` on unescaped delimiters outside of marked nested values, resolving
+    /// `\<delimiter>` and `\\`.
+    fn split(&self, args: &str) -> Vec<String> {
+        let mut parts = vec![String::new()];
+        let mut chars = args.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                PROTECTED_VALUE_START => match marked_value(chars.as_str()) {
+                    Some((value, tail)) => {
+                        parts.last_mut().unwrap().push_str(value);
+                        chars = tail.chars();
+                    }
+                    None => parts.last_mut().unwrap().push(c),
+                },
+                '\\' => match chars.next() {
+                    Some(next) if next == self.delimiter || next == '\\' => {
+                        parts.last_mut().unwrap().push(next)
+                    }
+                    Some(next) => parts.last_mut().unwrap().extend(['\\', next]),
+                    None => parts.last_mut().unwrap().push('\\'),
+                },
+                c if c == self.delimiter => parts.push(String::new()),
+                c => parts.last_mut().unwrap().push(c),
+            }
+        }
+
+        parts
+    }
+}
+
+/// Splits a nested value marked as `<length>\u{E001}<value>` off the front of `rest`.
+fn marked_value(rest: &str) -> Option<(&str, &str)> {
+    let (length, tail) = rest.split_once(PROTECTED_VALUE_END)?;
+    let length = length.parse::<usize>().ok()?;
+    let end = match tail.char_indices().nth(length) {
+        Some((end, _)) => end,
+        None if tail.chars().count() == length => tail.len(),
+        None => return None,
+    };
+
+    Some(tail.split_at(end))
+}
+
+impl Default for CoalesceCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl JakartaCommand for CoalesceCommand {
+    async fn try_process(
+        &mut self,
+        _: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        self.split(&args)
+            .into_iter()
+            .map(|part| part.trim().to_owned())
+            .find(|part| !part.is_empty())
+            .ok_or_else(|| CommandError(format!("All arguments of {args:?} are empty")))
+    }
+
+    fn marks_nested_values(&self) -> bool {
+        true
+    }
+
+    fn identifiers(&self) -> Vec<&'static str> {
+        vec!["coalesce"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::{Jakarta, JakartaBuilder};
+
+    struct LookupCommand {
+        values: HashMap<&'static str, &'static str>,
+    }
+
+    #[async_trait]
+    impl JakartaCommand for LookupCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            default_value: Option<String>,
+        ) -> String {
+            match self.values.get(args.as_str()) {
+                Some(value) => value.to_string(),
+                None => default_value.unwrap_or_default(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn it_resolves_the_first_non_empty_argument() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert(
+            "env",
+            Arc::new(Mutex::new(LookupCommand {
+                values: HashMap::from([("EMPTY", ""), ("B", "from_b")]),
+            })),
+        );
+        commands.insert("coalesce", Arc::new(Mutex::new(CoalesceCommand::new())));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "[${coalesce:${env:A}:${env:EMPTY}:literal}] [${coalesce:${env:A}:${env:B}:literal}] [${coalesce:${env:A}:http\\://x}] [${coalesce:${env:A}::-none}]"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "[literal] [from_b] [http://x] [none]".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_keeps_delimiters_in_resolved_values() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert(
+            "env",
+            Arc::new(Mutex::new(LookupCommand {
+                values: HashMap::from([("URL", "http://host:80"), ("EMPTY", "")]),
+            })),
+        );
+        commands.insert("coalesce", Arc::new(Mutex::new(CoalesceCommand::new())));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "[${coalesce:${env:EMPTY}:${env:URL}:literal}] [${coalesce:${env:EMPTY}:a${env:URL}b:c}]"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "[http://host:80] [ahttp://host:80b]".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_keeps_forged_markers_in_resolved_values() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert(
+            "env",
+            Arc::new(Mutex::new(LookupCommand {
+                values: HashMap::from([("FORGED", "\u{E001}:b\u{E000}"), ("EMPTY", "")]),
+            })),
+        );
+        commands.insert("coalesce", Arc::new(Mutex::new(CoalesceCommand::new())));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("[${coalesce:${env:EMPTY}:${env:FORGED}:c}]".to_owned())
+            .await;
+
+        assert_eq!(result, "[\u{E001}:b\u{E000}]".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_splits_on_custom_delimiters() {
+        let jakarta = JakartaBuilder::new()
+            .register_auto(CoalesceCommand::new().with_delimiter(','))
+            .build()
+            .unwrap();
+
+        let result = jakarta
+            .interpolate_string("${coalesce: , \\,,a:b}".to_owned())
+            .await;
+
+        assert_eq!(result, ",".to_owned());
+    }
+}
@@ -1,11 +1,156 @@
 use async_trait::async_trait;
+use thiserror::Error;
 
+use crate::context::Context;
+
+/// Returned by `try_process` when a command couldn't produce a value, e.g. on a timeout.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{0}")]
+pub struct CommandError(pub String);
+
+/// Commands implement either `process`, if they can't fail, or `try_process`, which `process` and
+/// `process_with_context` then fall back to the default value from. Implementing neither recurses
+/// endlessly.
 #[async_trait]
-pub trait JakartaCommand {
+pub trait JakartaCommand: Send {
     async fn process(
         &mut self,
         command: String,
+        instance: Option<String>,
+        args: String,
+        field: Option<String>,
+        default_value: Option<String>,
+    ) -> String {
+        self.process_with_context(
+            &Context::new(),
+            command,
+            instance,
+            args,
+            field,
+            default_value,
+        )
+        .await
+    }
+
+    /// Like `process`, but with the [`Context`] passed to `interpolate_string_with_context`.
+    /// Resolves through `try_process`, reporting an `Err` to the context's diagnostics and falling
+    /// back to the default value.
+    async fn process_with_context(
+        &mut self,
+        context: &Context,
+        command: String,
+        instance: Option<String>,
+        args: String,
+        field: Option<String>,
+        default_value: Option<String>,
+    ) -> String {
+        match self
+            .try_process(
+                context,
+                command,
+                instance,
+                args,
+                field,
+                default_value.clone(),
+            )
+            .await
+        {
+            Ok(value) => value,
+            Err(err) => {
+                context
+                    .diagnostics()
+                    .warn(&format!("{err}, resolving to default value"));
+
+                default_value.unwrap_or_else(|| "".to_owned())
+            }
+        }
+    }
+
+    /// Fallible variant of `process_with_context`, which interpolation resolves placeholders
+    /// through. An `Err` is what makes a placeholder fall back to its default value, fail
+    /// `try_interpolate_string` and count as a failure. Commands that need the context or can fail
+    /// implement it; without an override, the default value is handed to `process`, the command
+    /// never fails and only empty values fall back to a `:-` default.
+    async fn try_process(
+        &mut self,
+        _context: &Context,
+        command: String,
+        instance: Option<String>,
         args: String,
+        field: Option<String>,
         default_value: Option<String>,
-    ) -> String;
+    ) -> Result<String, CommandError> {
+        Ok(self
+            .process(command, instance, args, field, default_value)
+            .await)
+    }
+
+    /// Resolves a `${cmd:args[*]}` placeholder to multiple values, which get joined by the
+    /// placeholder's separator.
+    async fn process_multi(
+        &mut self,
+        command: String,
+        instance: Option<String>,
+        args: String,
+        field: Option<String>,
+        default_value: Option<String>,
+    ) -> Vec<String> {
+        vec![
+            self.process(command, instance, args, field, default_value)
+                .await,
+        ]
+    }
+
+    fn cacheable(&self) -> bool {
+        true
+    }
+
+    fn expand_result(&self) -> bool {
+        true
+    }
+
+    fn is_secret(&self) -> bool {
+        false
+    }
+
+    /// Whether values of placeholders nested in args get prefixed with their length in chars,
+    /// wrapped in `'\u{E000}'` and `'\u{E001}'`, so a command splitting its args can tell resolved
+    /// values apart from the delimiters written in the template.
+    fn marks_nested_values(&self) -> bool {
+        false
+    }
+
+    fn end_interpolation(&mut self) {}
+
+    /// The command ids this command handles, used by [`crate::JakartaBuilder::register_auto`].
+    fn identifiers(&self) -> Vec<&'static str> {
+        vec![]
+    }
+}
+
+/// Strips a single trailing `\n` or `\r\n`, the way shells treat command substitution output.
+/// Commands apply this unless the placeholder selects the `#raw` field.
+pub fn trim_trailing_newline(mut value: String) -> String {
+    if value.ends_with('\n') {
+        value.pop();
+
+        if value.ends_with('\r') {
+            value.pop();
+        }
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_trims_a_single_trailing_newline() {
+        assert_eq!(trim_trailing_newline("1\n".to_owned()), "1");
+        assert_eq!(trim_trailing_newline("1\r\n".to_owned()), "1");
+        assert_eq!(trim_trailing_newline("1\n\n".to_owned()), "1\n");
+        assert_eq!(trim_trailing_newline("1 ".to_owned()), "1 ");
+    }
 }
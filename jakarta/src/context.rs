@@ -0,0 +1,60 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::Arc,
+};
+
+use crate::diagnostics::{Diagnostics, TracingDiagnostics};
+
+/// Per-call values handed to [`crate::JakartaCommand::process_with_context`], keyed by type,
+/// e.g. a request-scoped trace id or credentials.
+#[derive(Clone, Default)]
+pub struct Context {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<T: Any + Send + Sync>(mut self, value: T) -> Self {
+        self.insert(value);
+        self
+    }
+
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) {
+        self.values.insert(TypeId::of::<T>(), Arc::new(value));
+    }
+
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+    }
+
+    /// The sink commands should report warnings and errors to, see [`Diagnostics`].
+    pub fn diagnostics(&self) -> Arc<dyn Diagnostics> {
+        match self.get::<Arc<dyn Diagnostics>>() {
+            Some(diagnostics) => diagnostics.clone(),
+            None => Arc::new(TracingDiagnostics),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct RequestId(&'static str);
+
+    #[test]
+    fn it_stores_values_by_type() {
+        let context = Context::new().with(RequestId("req-1")).with(42u16);
+
+        assert_eq!(context.get::<RequestId>(), Some(&RequestId("req-1")));
+        assert_eq!(context.get::<u16>(), Some(&42));
+        assert_eq!(context.get::<String>(), None);
+    }
+}
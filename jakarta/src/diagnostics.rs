@@ -0,0 +1,159 @@
+use std::sync::{Arc, Mutex};
+
+/// Receives the warnings and errors of an interpolation. Defaults to [`TracingDiagnostics`], but
+/// can be swapped out, e.g. to collect messages for a CLI to print once it's done.
+pub trait Diagnostics: Send + Sync {
+    fn warn(&self, message: &str);
+
+    fn error(&self, message: &str);
+}
+
+/// Forwards diagnostics to `tracing::warn!` and `tracing::error!`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingDiagnostics;
+
+impl Diagnostics for TracingDiagnostics {
+    fn warn(&self, message: &str) {
+        tracing::warn!("{message}");
+    }
+
+    fn error(&self, message: &str) {
+        tracing::error!("{message}");
+    }
+}
+
+/// Records the messages of the placeholder being resolved while still forwarding them to `inner`,
+/// so `interpolate_collecting` can attribute them.
+pub(crate) struct CollectingDiagnostics {
+    inner: Arc<dyn Diagnostics>,
+    messages: Mutex<Vec<String>>,
+}
+
+impl CollectingDiagnostics {
+    pub(crate) fn new(inner: Arc<dyn Diagnostics>) -> Self {
+        Self {
+            inner,
+            messages: Mutex::default(),
+        }
+    }
+
+    pub(crate) fn take(&self) -> Vec<String> {
+        self.messages
+            .lock()
+            .map(|mut messages| std::mem::take(&mut *messages))
+            .unwrap_or_default()
+    }
+
+    fn push(&self, message: &str) {
+        if let Ok(mut messages) = self.messages.lock() {
+            messages.push(message.to_owned());
+        }
+    }
+}
+
+impl Diagnostics for CollectingDiagnostics {
+    fn warn(&self, message: &str) {
+        self.push(message);
+        self.inner.warn(message);
+    }
+
+    fn error(&self, message: &str) {
+        self.push(message);
+        self.inner.error(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use async_trait::async_trait;
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::{CommandError, Context, FallbackCommand, JakartaBuilder, JakartaCommand};
+
+    struct FailingCommand {}
+
+    #[async_trait]
+    impl JakartaCommand for FailingCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            _: String,
+            _: Option<String>,
+            default_value: Option<String>,
+        ) -> String {
+            default_value.unwrap_or_default()
+        }
+
+        async fn try_process(
+            &mut self,
+            _: &Context,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> Result<String, CommandError> {
+            Err(CommandError(format!("{args} is unavailable")))
+        }
+    }
+
+    struct ContextCommand {}
+
+    #[async_trait]
+    impl JakartaCommand for ContextCommand {
+        async fn try_process(
+            &mut self,
+            context: &Context,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> Result<String, CommandError> {
+            context.diagnostics().error(&format!("{args} failed"));
+
+            Ok("".to_owned())
+        }
+    }
+
+    #[tokio::test]
+    async fn it_collects_diagnostics() {
+        let diagnostics = Arc::new(CollectingDiagnostics::new(Arc::new(TracingDiagnostics)));
+        let failing_cmd: Arc<Mutex<dyn JakartaCommand>> = Arc::new(Mutex::new(FailingCommand {}));
+
+        let jakarta = JakartaBuilder::new()
+            .commands(HashMap::from([
+                (
+                    "fallback",
+                    Arc::new(Mutex::new(FallbackCommand::new(vec![(
+                        "fail",
+                        failing_cmd,
+                    )]))) as Arc<Mutex<dyn JakartaCommand>>,
+                ),
+                ("ctx", Arc::new(Mutex::new(ContextCommand {}))),
+            ]))
+            .diagnostics(diagnostics.clone())
+            .build()
+            .unwrap();
+
+        let result = jakarta
+            .interpolate_string("[${fallback:a:-x}] [${ctx:b}] [${ctx:c | nope}]".to_owned())
+            .await;
+        assert_eq!(result, "[x] [] []".to_owned());
+
+        assert_eq!(
+            diagnostics.take(),
+            vec![
+                "Fallback source \"fail\" failed for \"a\": a is unavailable".to_owned(),
+                "None of the 1 sources resolved \"a\", resolving to default value".to_owned(),
+                "b failed".to_owned(),
+                "c failed".to_owned(),
+                "Unknown filter \"nope\", leaving value unchanged".to_owned(),
+            ]
+        );
+    }
+}
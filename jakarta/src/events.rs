@@ -0,0 +1,161 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use crate::jakarta::{InterpolationState, Jakarta};
+
+/// Progress of an interpolation started with `Jakarta::interpolate_stream`. Placeholders of
+/// secret commands are redacted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolutionEvent {
+    /// `placeholder` started resolving through `command`.
+    Resolving {
+        placeholder: String,
+        command: String,
+    },
+    /// `placeholder` was resolved, either by its command or from the cache.
+    Resolved {
+        placeholder: String,
+        command: String,
+        elapsed: Duration,
+    },
+    /// The last event, carrying the interpolated string.
+    Completed(String),
+}
+
+/// A [`Stream`] of [`ResolutionEvent`]s that drives the interpolation, which only makes progress
+/// while the stream is polled.
+pub struct ResolutionStream<'j> {
+    interpolation: Option<Pin<Box<dyn Future<Output = String> + Send + 'j>>>,
+    events: UnboundedReceiver<ResolutionEvent>,
+    completed: Option<String>,
+}
+
+impl Stream for ResolutionStream<'_> {
+    type Item = ResolutionEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(event)) = this.events.poll_recv(cx) {
+                return Poll::Ready(Some(event));
+            }
+
+            // Once the interpolation is done, its sender is dropped and the remaining events were
+            // drained above.
+            let Some(pending) = &mut this.interpolation else {
+                return Poll::Ready(this.completed.take().map(ResolutionEvent::Completed));
+            };
+
+            match pending.as_mut().poll(cx) {
+                Poll::Ready(interpolated_string) => {
+                    this.interpolation = None;
+                    this.completed = Some(interpolated_string);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<'a> Jakarta<'a> {
+    /// Interpolates `original`, emitting an event whenever a placeholder starts and finishes
+    /// resolving, e.g. to show progress in a UI.
+    pub fn interpolate_stream<'j>(&'j self, original: String) -> ResolutionStream<'j> {
+        let (sender, events) = mpsc::unbounded_channel();
+
+        let interpolation = async move {
+            let mut state = InterpolationState::default();
+            state.events = Some(sender);
+
+            self.interpolate(original, &mut state).await
+        };
+
+        ResolutionStream {
+            interpolation: Some(Box::pin(interpolation)),
+            events,
+            completed: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use async_trait::async_trait;
+    use tokio::sync::Mutex;
+    use tokio_stream::StreamExt;
+
+    use super::*;
+    use crate::JakartaCommand;
+
+    struct EchoCommand {}
+
+    #[async_trait]
+    impl JakartaCommand for EchoCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> String {
+            tokio::task::yield_now().await;
+
+            args
+        }
+    }
+
+    #[tokio::test]
+    async fn it_streams_resolution_events() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("vault", Arc::new(Mutex::new(EchoCommand {})));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let mut stream = jakarta
+            .interpolate_stream("${vault:db} ${vault:${vault:a}} ${vault:db} ${x}".to_owned());
+
+        let mut events = vec![];
+        while let Some(event) = stream.next().await {
+            events.push(match event {
+                ResolutionEvent::Resolving {
+                    placeholder,
+                    command,
+                } => format!("resolving {command} {placeholder}"),
+                ResolutionEvent::Resolved {
+                    placeholder,
+                    command,
+                    ..
+                } => format!("resolved {command} {placeholder}"),
+                ResolutionEvent::Completed(interpolated_string) => {
+                    format!("completed {interpolated_string}")
+                }
+            });
+        }
+
+        assert_eq!(
+            events,
+            vec![
+                "resolving vault ${vault:db}",
+                "resolved vault ${vault:db}",
+                "resolving vault ${vault:a}",
+                "resolved vault ${vault:a}",
+                "resolving vault ${vault:${vault:a}}",
+                "resolved vault ${vault:${vault:a}}",
+                "resolving vault ${vault:db}",
+                "resolved vault ${vault:db}",
+                "completed db a db ",
+            ]
+        );
+        assert_eq!(stream.next().await, None);
+    }
+}
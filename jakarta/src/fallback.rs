@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use crate::{
+    commands::{CommandError, JakartaCommand},
+    context::Context,
+};
+
+/// Tries each source in order and resolves to the first non-empty value, e.g.
+/// `FallbackCommand::new(vec![("kv2", vault), ("env", env)])`. Each source receives the
+/// placeholder with the command id it's paired with, so commands handling several ids know which
+/// one is meant.
+pub struct FallbackCommand {
+    sources: Vec<(String, Arc<Mutex<dyn JakartaCommand>>)>,
+}
+
+impl FallbackCommand {
+    pub fn new(sources: Vec<(&str, Arc<Mutex<dyn JakartaCommand>>)>) -> Self {
+        Self {
+            sources: sources
+                .into_iter()
+                .map(|(command, source)| (command.to_owned(), source))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl JakartaCommand for FallbackCommand {
+    async fn try_process(
+        &mut self,
+        context: &Context,
+        _: String,
+        instance: Option<String>,
+        args: String,
+        field: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        for (command, source) in &self.sources {
+            let result = source
+                .lock()
+                .await
+                .try_process(
+                    context,
+                    command.clone(),
+                    instance.clone(),
+                    args.clone(),
+                    field.clone(),
+                    None,
+                )
+                .await;
+
+            match result {
+                Ok(value) if !value.is_empty() => return Ok(value),
+                Ok(_) => {}
+                Err(err) => context.diagnostics().warn(&format!(
+                    "Fallback source {command:?} failed for {args:?}: {err}"
+                )),
+            }
+        }
+
+        Err(CommandError(format!(
+            "None of the {} sources resolved {args:?}",
+            self.sources.len()
+        )))
+    }
+
+    fn cacheable(&self) -> bool {
+        self.sources
+            .iter()
+            .all(|(_, source)| source.try_lock().is_ok_and(|source| source.cacheable()))
+    }
+
+    fn expand_result(&self) -> bool {
+        self.sources
+            .iter()
+            .all(|(_, source)| source.try_lock().is_ok_and(|source| source.expand_result()))
+    }
+
+    fn is_secret(&self) -> bool {
+        self.sources
+            .iter()
+            .any(|(_, source)| source.try_lock().map_or(true, |source| source.is_secret()))
+    }
+
+    fn end_interpolation(&mut self) {
+        for (_, source) in &self.sources {
+            if let Ok(mut source) = source.try_lock() {
+                source.end_interpolation();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::Jakarta;
+
+    struct LookupCommand {
+        values: HashMap<&'static str, &'static str>,
+        lookups: usize,
+    }
+
+    #[async_trait]
+    impl JakartaCommand for LookupCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            default_value: Option<String>,
+        ) -> String {
+            self.lookups += 1;
+
+            match self.values.get(args.as_str()) {
+                Some(value) => value.to_string(),
+                None => default_value.unwrap_or_default(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_later_sources() {
+        let vault_cmd = Arc::new(Mutex::new(LookupCommand {
+            values: HashMap::from([("DB_PASSWORD", "from_vault"), ("EMPTY", "")]),
+            lookups: 0,
+        }));
+        let env_cmd = Arc::new(Mutex::new(LookupCommand {
+            values: HashMap::from([("EMPTY", "from_env"), ("API_KEY", "from_env")]),
+            lookups: 0,
+        }));
+
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert(
+            "secret",
+            Arc::new(Mutex::new(FallbackCommand::new(vec![
+                ("vault", vault_cmd.clone()),
+                ("env", env_cmd.clone()),
+            ]))),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${secret:DB_PASSWORD} ${secret:EMPTY} ${secret:API_KEY} ${secret:MISSING:-literal}"
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "from_vault from_env from_env literal".to_owned());
+        assert_eq!(vault_cmd.lock().await.lookups, 4);
+        assert_eq!(env_cmd.lock().await.lookups, 3);
+    }
+}
@@ -0,0 +1,372 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use regex::Regex;
+
+use crate::diagnostics::{Diagnostics, TracingDiagnostics};
+
+pub type Filter = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// A filter taking `:` separated arguments, as in `replace:from:to`.
+pub type ParameterizedFilter = Arc<dyn Fn(&str, &[String]) -> Option<String> + Send + Sync>;
+
+#[derive(Clone)]
+pub struct FilterRegistry {
+    filters: HashMap<String, Filter>,
+    parameterized_filters: HashMap<String, ParameterizedFilter>,
+}
+
+impl FilterRegistry {
+    pub fn empty() -> Self {
+        Self {
+            filters: HashMap::new(),
+            parameterized_filters: HashMap::new(),
+        }
+    }
+
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        filter: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) {
+        self.filters.insert(name.into(), Arc::new(filter));
+    }
+
+    pub fn register_parameterized(
+        &mut self,
+        name: impl Into<String>,
+        filter: impl Fn(&str, &[String]) -> Option<String> + Send + Sync + 'static,
+    ) {
+        self.parameterized_filters
+            .insert(name.into(), Arc::new(filter));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Filter> {
+        self.filters.get(name)
+    }
+
+    /// Runs `value` through the `|` separated filters of `pipeline`. Arguments follow the filter
+    /// name separated by `:`, literal colons and pipes are escaped as `\:` and `\|`.
+    pub fn apply(&self, value: String, pipeline: &str) -> String {
+        self.apply_with_diagnostics(value, pipeline, &TracingDiagnostics)
+    }
+
+    /// Like `apply`, reporting unknown and failing filters to `diagnostics`.
+    pub fn apply_with_diagnostics(
+        &self,
+        value: String,
+        pipeline: &str,
+        diagnostics: &dyn Diagnostics,
+    ) -> String {
+        stages(pipeline).fold(value, |value, (name, args)| {
+            let filtered = match (self.get(name), self.parameterized_filters.get(name)) {
+                (Some(filter), _) if args.is_empty() => filter(&value),
+                (_, Some(filter)) => filter(&value, &args),
+                _ => {
+                    diagnostics.warn(&format!("Unknown filter {name:?}, leaving value unchanged"));
+
+                    return value;
+                }
+            };
+
+            filtered.unwrap_or_else(|| {
+                diagnostics.warn(&format!(
+                    "Filter {name:?} could not be applied, leaving value unchanged"
+                ));
+
+                value
+            })
+        })
+    }
+
+    /// Returns the first filter of `pipeline` that isn't registered, or is called with arguments
+    /// it doesn't take.
+    pub fn unknown<'p>(&self, pipeline: &'p str) -> Option<&'p str> {
+        stages(pipeline)
+            .find(|(name, args)| {
+                !(self.filters.contains_key(*name) && args.is_empty()
+                    || self.parameterized_filters.contains_key(*name))
+            })
+            .map(|(name, _)| name)
+    }
+}
+
+/// Splits `pipeline` into filter names and their unescaped arguments.
+fn stages(pipeline: &str) -> impl Iterator<Item = (&str, Vec<String>)> {
+    split_unescaped(pipeline, PIPE)
+        .into_iter()
+        .map(str::trim)
+        .filter(|filter| !filter.is_empty())
+        .map(|filter| {
+            let mut parts = split_unescaped(filter, ":").into_iter();
+            let name = parts.next().unwrap_or_default().trim();
+
+            (name, parts.map(unescape).collect())
+        })
+}
+
+impl Default for FilterRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+
+        registry.register("upper", |value| Some(value.to_uppercase()));
+        registry.register("lower", |value| Some(value.to_lowercase()));
+        registry.register("trim", |value| Some(value.trim().to_owned()));
+        registry.register("base64encode", |value| Some(STANDARD.encode(value)));
+        registry.register("base64decode", |value| {
+            STANDARD
+                .decode(value)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+        });
+        registry.register("urlencode", |value| Some(url_encode(value)));
+        registry.register("snake", |value| Some(words(value).join("_")));
+        registry.register("kebab", |value| Some(words(value).join("-")));
+        registry.register("screaming_snake", |value| {
+            Some(words(value).join("_").to_uppercase())
+        });
+        registry.register("camel", |value| {
+            let words = words(value);
+            let (first, rest) = words.split_first()?;
+
+            Some(
+                rest.iter()
+                    .fold(first.clone(), |camel, word| camel + &capitalize(word)),
+            )
+        });
+        registry.register("pascal", |value| {
+            Some(words(value).iter().map(|word| capitalize(word)).collect())
+        });
+        registry.register_parameterized("replace", |value, args| {
+            let (from, to) = from_to(args)?;
+
+            Some(value.replace(&from, to))
+        });
+
+        let patterns: Mutex<HashMap<String, Regex>> = Mutex::default();
+        registry.register_parameterized("regex_replace", move |value, args| {
+            let (pattern, replacement) = from_to(args)?;
+            let mut patterns = patterns.lock().ok()?;
+
+            let regex = match patterns.get(&pattern) {
+                Some(regex) => regex,
+                None => {
+                    let regex = Regex::new(&pattern).ok()?;
+                    patterns.entry(pattern).or_insert(regex)
+                }
+            };
+
+            Some(regex.replace_all(value, replacement.as_str()).into_owned())
+        });
+
+        registry
+    }
+}
+
+/// Separates the filters of a pipeline, as in `${env:FOO | trim | upper}`. Args containing a
+/// literal `|`, like shell pipelines, need to be quoted or escaped as `\|`.
+pub(crate) const PIPE: &str = "|";
+
+/// Splits on `separator`, skipping occurrences escaped with a backslash.
+fn split_unescaped<'v>(value: &'v str, separator: &str) -> Vec<&'v str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut idx = 0;
+
+    while let Some(rest) = value.get(idx..).filter(|rest| !rest.is_empty()) {
+        if let Some(escaped) = rest.strip_prefix('\\') {
+            idx += 1 + escaped.chars().next().map_or(0, char::len_utf8);
+        } else if rest.starts_with(separator) {
+            parts.push(&value[start..idx]);
+            idx += separator.len();
+            start = idx;
+        } else {
+            idx += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    parts.push(&value[start..]);
+
+    parts
+}
+
+/// Resolves `\:` and `\|`, leaving all other backslashes (like regex escapes) untouched.
+fn unescape(value: &str) -> String {
+    value.replace("\\:", ":").replace("\\|", PIPE)
+}
+
+/// Splits the arguments of `name:from:to` on the last separator, so unescaped colons in `from`
+/// (as in `replace:https://:`) are kept as-is.
+fn from_to(args: &[String]) -> Option<(String, &String)> {
+    let (to, from) = args.split_last()?;
+
+    (!from.is_empty()).then(|| (from.join(":"), to))
+}
+
+/// Percent-encodes everything but the unreserved characters of RFC 3986.
+const URL_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+fn url_encode(value: &str) -> String {
+    utf8_percent_encode(value, URL_ENCODE_SET).to_string()
+}
+
+/// Splits an identifier like `SERVICE_NAME`, `my-service` or `HTTPServerName` into lowercase
+/// words, breaking on separators and case changes.
+fn words(value: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let chars = value.chars().collect::<Vec<_>>();
+
+    for (idx, c) in chars.iter().copied().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        let previous = idx.checked_sub(1).map(|idx| chars[idx]);
+        let next = chars.get(idx + 1).copied();
+        let boundary = c.is_uppercase()
+            && previous.is_some_and(|previous| {
+                previous.is_lowercase()
+                    || previous.is_numeric()
+                    || (previous.is_uppercase() && next.is_some_and(char::is_lowercase))
+            });
+
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.extend(c.to_lowercase());
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_encodes_values() {
+        let registry = FilterRegistry::default();
+
+        assert_eq!(
+            registry.apply("fooba".to_owned(), "base64encode"),
+            "Zm9vYmE="
+        );
+        assert_eq!(
+            registry.apply("Zm9vYmE=".to_owned(), "base64decode"),
+            "fooba"
+        );
+        assert_eq!(
+            registry.apply("not base64!".to_owned(), "base64decode"),
+            "not base64!"
+        );
+        assert_eq!(
+            registry.apply("a b&c=d/é-_.~".to_owned(), "urlencode"),
+            "a%20b%26c%3Dd%2F%C3%A9-_.~"
+        );
+    }
+
+    #[test]
+    fn it_converts_identifier_cases() {
+        let registry = FilterRegistry::default();
+
+        for value in [
+            "MY_SERVICE_NAME",
+            "my-service-name",
+            "myServiceName",
+            "My service name",
+        ] {
+            assert_eq!(registry.apply(value.to_owned(), "snake"), "my_service_name");
+            assert_eq!(registry.apply(value.to_owned(), "kebab"), "my-service-name");
+            assert_eq!(registry.apply(value.to_owned(), "camel"), "myServiceName");
+            assert_eq!(registry.apply(value.to_owned(), "pascal"), "MyServiceName");
+            assert_eq!(
+                registry.apply(value.to_owned(), "screaming_snake"),
+                "MY_SERVICE_NAME"
+            );
+        }
+
+        assert_eq!(
+            registry.apply("HTTPServer2Go".to_owned(), "snake"),
+            "http_server2_go"
+        );
+        assert_eq!(registry.apply("".to_owned(), "camel"), "");
+    }
+
+    #[test]
+    fn it_replaces_literals() {
+        let registry = FilterRegistry::default();
+
+        assert_eq!(
+            registry.apply("https://example.com".to_owned(), "replace:https://:"),
+            "example.com"
+        );
+        assert_eq!(
+            registry.apply("a-b-c".to_owned(), "replace:-:\\: | upper"),
+            "A:B:C"
+        );
+        assert_eq!(registry.apply("a|b".to_owned(), "replace:\\|:/"), "a/b");
+        assert_eq!(registry.apply("abc".to_owned(), "replace:b"), "abc");
+    }
+
+    #[test]
+    fn it_replaces_regex_matches() {
+        let registry = FilterRegistry::default();
+
+        assert_eq!(
+            registry.apply(
+                "v1.2.3-rc1".to_owned(),
+                "regex_replace:^v(\\d+)\\.(\\d+).*$:$1-$2"
+            ),
+            "1-2"
+        );
+        assert_eq!(
+            registry.apply("a1b22c".to_owned(), "regex_replace:\\d+:_"),
+            "a_b_c"
+        );
+        assert_eq!(
+            registry.apply("x1y22".to_owned(), "regex_replace:\\d+\\|x:_ | upper"),
+            "__Y_"
+        );
+        assert_eq!(
+            registry.apply("unchanged".to_owned(), "regex_replace:(:_"),
+            "unchanged"
+        );
+    }
+
+    #[test]
+    fn it_applies_custom_filters() {
+        let mut registry = FilterRegistry::default();
+        registry.register("reverse", |value| Some(value.chars().rev().collect()));
+
+        assert_eq!(
+            registry.apply(" abc ".to_owned(), "trim | reverse | upper"),
+            "CBA"
+        );
+        assert_eq!(registry.unknown("trim | reverse | replace:a:b"), None);
+        assert_eq!(registry.unknown("trim | tr a b"), Some("tr a b"));
+        assert_eq!(registry.unknown("upper:x"), Some("upper"));
+    }
+}
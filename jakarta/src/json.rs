@@ -0,0 +1,170 @@
+use serde_json::{Map, Value};
+
+use crate::jakarta::{Jakarta, JakartaError, OnKeyCollision};
+
+impl<'a> Jakarta<'a> {
+    /// Interpolates every string leaf of a JSON [`Value`] in place, so resolved values never need
+    /// to be escaped and literal `${` in other leaves can't break the document.
+    pub async fn interpolate_json(&self, value: Value) -> Result<Value, JakartaError> {
+        interpolate_value(self, value, None).await
+    }
+
+    /// Like `interpolate_json`, but also interpolates object keys. Keys of the same object that
+    /// resolve to the same name are handled according to `on_collision`.
+    pub async fn interpolate_json_with_keys(
+        &self,
+        value: Value,
+        on_collision: OnKeyCollision,
+    ) -> Result<Value, JakartaError> {
+        interpolate_value(self, value, Some(on_collision)).await
+    }
+}
+
+/// `keys` is `None` if keys are left as is.
+async fn interpolate_value(
+    jakarta: &Jakarta<'_>,
+    value: Value,
+    keys: Option<OnKeyCollision>,
+) -> Result<Value, JakartaError> {
+    Ok(match value {
+        Value::String(value) => Value::String(jakarta.try_interpolate_string(value).await?),
+        Value::Array(values) => {
+            let mut interpolated_values = Vec::with_capacity(values.len());
+            for value in values {
+                interpolated_values.push(Box::pin(interpolate_value(jakarta, value, keys)).await?);
+            }
+
+            Value::Array(interpolated_values)
+        }
+        Value::Object(entries) => {
+            let mut interpolated_entries = Map::with_capacity(entries.len());
+            for (key, value) in entries {
+                let value = Box::pin(interpolate_value(jakarta, value, keys)).await?;
+
+                let key = match keys {
+                    Some(on_collision) => {
+                        let key = jakarta.try_interpolate_string(key).await?;
+
+                        if interpolated_entries.shift_remove(&key).is_some()
+                            && on_collision == OnKeyCollision::Error
+                        {
+                            return Err(JakartaError::KeyCollision(key));
+                        }
+
+                        key
+                    }
+                    None => key,
+                };
+
+                interpolated_entries.insert(key, value);
+            }
+
+            Value::Object(interpolated_entries)
+        }
+        value => value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use async_trait::async_trait;
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::JakartaCommand;
+
+    struct TestCommand {}
+
+    #[async_trait]
+    impl JakartaCommand for TestCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> String {
+            format!("\"{args}\": ok")
+        }
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_string_leaves() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        commands.insert("test", Arc::new(Mutex::new(TestCommand {})));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let value = r#"{"${test:key}":"${test:a}","n":1.5,"b":true,"z":null,"l":["${test:b}",{"c":"x ${test:c}"},[2]]}"#
+            .parse::<Value>()
+            .unwrap();
+
+        let result = jakarta.interpolate_json(value).await.unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            r#"{"${test:key}":"\"a\": ok","n":1.5,"b":true,"z":null,"l":["\"b\": ok",{"c":"x \"c\": ok"},[2]]}"#
+        );
+        assert_eq!(result.to_string().parse::<Value>().unwrap(), result);
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_keys() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        commands.insert("test", Arc::new(Mutex::new(TestCommand {})));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let value = r#"{"${test:prefix}_timeout":30,"nested":{"${test:a}":["${test:b}"]}}"#
+            .parse::<Value>()
+            .unwrap();
+
+        let result = jakarta
+            .interpolate_json_with_keys(value, OnKeyCollision::Error)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            r#"{"\"prefix\": ok_timeout":30,"nested":{"\"a\": ok":["\"b\": ok"]}}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn it_handles_key_collisions() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        commands.insert("test", Arc::new(Mutex::new(TestCommand {})));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let value = r#"{"\"a\": ok":1,"b":2,"${test:a}":3}"#.parse::<Value>().unwrap();
+
+        let result = jakarta
+            .interpolate_json_with_keys(value.clone(), OnKeyCollision::Error)
+            .await;
+        assert!(matches!(result, Err(JakartaError::KeyCollision(key)) if key == "\"a\": ok"));
+
+        let result = jakarta
+            .interpolate_json_with_keys(value, OnKeyCollision::LastWins)
+            .await
+            .unwrap();
+        assert_eq!(result.to_string(), r#"{"b":2,"\"a\": ok":3}"#);
+    }
+
+    #[tokio::test]
+    async fn it_propagates_interpolation_errors() {
+        let jakarta = Jakarta::new(HashMap::new()).unwrap();
+
+        let value = r#"{"a":["${missing!}"]}"#.parse::<Value>().unwrap();
+
+        let result = jakarta.interpolate_json(value).await;
+
+        assert!(matches!(
+            result,
+            Err(JakartaError::RequiredPlaceholdersEmpty(_))
+        ));
+    }
+}
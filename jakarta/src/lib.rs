@@ -1,5 +1,48 @@
+mod builder;
+mod cache_layer;
+mod closure;
+mod coalesce;
 mod commands;
+mod context;
+mod diagnostics;
+mod events;
+mod fallback;
+mod filters;
 mod jakarta;
+#[cfg(feature = "json")]
+mod json;
+mod map;
+mod metrics;
+mod parser;
+mod passthrough;
+mod plan;
+mod reference;
+mod report;
+mod retry;
+mod secret;
+mod selector;
+mod stream;
 
-pub use crate::commands::JakartaCommand;
+pub use crate::builder::JakartaBuilder;
+pub use crate::cache_layer::{CacheLayer, Clock};
+pub use crate::closure::{FnCommand, SyncFnCommand};
+pub use crate::coalesce::CoalesceCommand;
+pub use crate::commands::{trim_trailing_newline, CommandError, JakartaCommand};
+pub use crate::context::Context;
+pub use crate::diagnostics::{Diagnostics, TracingDiagnostics};
+pub use crate::events::{ResolutionEvent, ResolutionStream};
+pub use crate::fallback::FallbackCommand;
+pub use crate::filters::{Filter, FilterRegistry, ParameterizedFilter};
 pub use crate::jakarta::*;
+#[cfg(feature = "metrics")]
+pub use crate::metrics::MetricsCrateRecorder;
+pub use crate::metrics::MetricsRecorder;
+pub use crate::parser::{parse_placeholder, ParsedPlaceholder};
+pub use crate::passthrough::PassthroughCommand;
+pub use crate::plan::*;
+pub use crate::reference::{ResolvedEntries, SelfCommand};
+pub use crate::report::*;
+pub use crate::retry::RetryCommand;
+pub use crate::secret::*;
+pub use crate::selector::FieldSelector;
+pub use tokio_util::sync::CancellationToken;
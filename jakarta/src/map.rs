@@ -0,0 +1,314 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    jakarta::{InterpolationState, Jakarta, JakartaError, OnKeyCollision},
+    reference::SELF_COMMAND_ID,
+};
+
+/// The entries of an `interpolate_map` batch, shared by every entry's interpolation. Entries are
+/// resolved on first reference, so references that only show up once nested placeholders are
+/// resolved, e.g. `${self:${env:KEY}}`, are followed too.
+#[derive(Default)]
+pub(crate) struct MapEntries {
+    values: HashMap<String, String>,
+    resolved: Mutex<HashMap<String, String>>,
+    /// The keys currently being resolved, outermost first.
+    resolving: Mutex<Vec<String>>,
+    cycle: Mutex<Option<Vec<String>>>,
+}
+
+impl MapEntries {
+    fn new(values: HashMap<String, String>) -> Self {
+        Self {
+            values,
+            ..Default::default()
+        }
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+}
+
+impl<'a> Jakarta<'a> {
+    /// Interpolates every value of `map`. Values may reference other entries as `${other_key}`
+    /// whenever no command is registered under that id, or through a registered
+    /// [`crate::SelfCommand`] as `${self:other_key}`; entries are resolved on first reference and
+    /// reference cycles are reported as [`JakartaError::ReferenceCycle`].
+    pub async fn interpolate_map(
+        &self,
+        map: HashMap<String, String>,
+    ) -> Result<HashMap<String, String>, JakartaError> {
+        let entries = self.resolve_map(map).await?;
+        let resolved = std::mem::take(&mut *entries.resolved.lock().unwrap());
+
+        Ok(resolved)
+    }
+
+    /// Like `interpolate_map`, but also interpolates the keys, e.g. `${env:PREFIX}_timeout`.
+    /// Keys are resolved in sorted order of the original keys, which decides the winner for
+    /// [`OnKeyCollision::LastWins`].
+    pub async fn interpolate_map_with_keys(
+        &self,
+        map: HashMap<String, String>,
+        on_collision: OnKeyCollision,
+    ) -> Result<HashMap<String, String>, JakartaError> {
+        let entries = self.resolve_map(map).await?;
+
+        let mut keys = entries.values.keys().collect::<Vec<_>>();
+        keys.sort_unstable();
+
+        let mut interpolated = HashMap::with_capacity(keys.len());
+        for key in keys {
+            let value = self.resolve_entry(&entries, key).await;
+
+            let mut state = InterpolationState::default();
+            state.references = Some(entries.clone());
+
+            let key = self.interpolate(key.clone(), &mut state).await;
+            if interpolated.insert(key.clone(), value).is_some()
+                && on_collision == OnKeyCollision::Error
+            {
+                return Err(JakartaError::KeyCollision(key));
+            }
+        }
+
+        Ok(interpolated)
+    }
+
+    async fn resolve_map(
+        &self,
+        map: HashMap<String, String>,
+    ) -> Result<Arc<MapEntries>, JakartaError> {
+        let entries = Arc::new(MapEntries::new(map));
+
+        let mut keys = entries.values.keys().collect::<Vec<_>>();
+        keys.sort_unstable();
+
+        for key in keys {
+            self.resolve_entry(&entries, key).await;
+
+            if let Some(cycle) = entries.cycle.lock().unwrap().take() {
+                return Err(JakartaError::ReferenceCycle(cycle));
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Returns the interpolated value of the `key` entry, interpolating it first if this is its
+    /// first reference. A reference back to an entry that's still being resolved records the
+    /// cycle and resolves to an empty value.
+    pub(crate) async fn resolve_entry(&self, entries: &Arc<MapEntries>, key: &str) -> String {
+        if let Some(value) = entries.resolved.lock().unwrap().get(key) {
+            return value.clone();
+        }
+
+        {
+            let mut resolving = entries.resolving.lock().unwrap();
+            if let Some(start) = resolving.iter().position(|resolving| resolving == key) {
+                let mut cycle = resolving[start..].to_vec();
+                cycle.push(key.to_owned());
+                entries.cycle.lock().unwrap().get_or_insert(cycle);
+
+                return "".to_owned();
+            }
+            resolving.push(key.to_owned());
+        }
+
+        let mut state = InterpolationState::default();
+        state.references = Some(entries.clone());
+
+        let value = Box::pin(self.interpolate(entries.values[key].clone(), &mut state)).await;
+
+        entries.resolving.lock().unwrap().pop();
+        entries
+            .resolved
+            .lock()
+            .unwrap()
+            .insert(key.to_owned(), value.clone());
+
+        value
+    }
+
+    /// Whether a placeholder of `command_id` with `args` references a map entry, returning the
+    /// entry's key.
+    pub(crate) fn entry_key<'k>(
+        &self,
+        entries: &MapEntries,
+        command_id: &'k str,
+        args: &'k str,
+    ) -> Option<&'k str> {
+        let key = match self.lookup_command(command_id) {
+            Some(_) if command_id == SELF_COMMAND_ID => args.trim(),
+            Some(_) => return None,
+            None => command_id,
+        };
+
+        entries.contains(key).then_some(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::JakartaCommand;
+
+    struct EnvCommand {}
+
+    #[async_trait]
+    impl JakartaCommand for EnvCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> String {
+            args.to_lowercase()
+        }
+    }
+
+    struct CountingCommand {
+        invocations: usize,
+    }
+
+    #[async_trait]
+    impl JakartaCommand for CountingCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            _: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> String {
+            self.invocations += 1;
+
+            self.invocations.to_string()
+        }
+    }
+
+    fn map(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_cross_referencing_keys() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("env", Arc::new(Mutex::new(EnvCommand {})));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_map(map(&[
+                ("url", "postgres://${host}:${port}/${env:APP}"),
+                ("host", "${env:DB_HOST}"),
+                ("port", "5432"),
+                ("banner", "$${host} is ${host|upper}"),
+                ("missing", "${unknown}"),
+            ]))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            map(&[
+                ("url", "postgres://db_host:5432/app"),
+                ("host", "db_host"),
+                ("port", "5432"),
+                ("banner", "${host} is DB_HOST"),
+                ("missing", ""),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn it_resolves_each_entry_once() {
+        let counting_cmd = Arc::new(Mutex::new(CountingCommand { invocations: 0 }));
+        let jakarta = Jakarta::builder()
+            .command("count", counting_cmd.clone())
+            .build()
+            .unwrap();
+
+        let result = jakarta
+            .interpolate_map(map(&[
+                ("a", "${shared}"),
+                ("b", "${shared}-${shared}"),
+                ("shared", "${count}"),
+            ]))
+            .await
+            .unwrap();
+
+        assert_eq!(result, map(&[("a", "1"), ("b", "1-1"), ("shared", "1")]));
+        assert_eq!(counting_cmd.lock().await.invocations, 1);
+    }
+
+    #[tokio::test]
+    async fn it_interpolates_keys() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("env", Arc::new(Mutex::new(EnvCommand {})));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_map_with_keys(
+                map(&[
+                    ("${env:PREFIX}_timeout", "30"),
+                    ("${prefix}_url", "${env:URL}"),
+                    ("prefix", "app"),
+                ]),
+                OnKeyCollision::Error,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            map(&[
+                ("prefix_timeout", "30"),
+                ("app_url", "url"),
+                ("prefix", "app")
+            ])
+        );
+
+        let colliding = map(&[("${env:A}", "1"), ("a", "2"), ("b", "3")]);
+
+        let result = jakarta
+            .interpolate_map_with_keys(colliding.clone(), OnKeyCollision::Error)
+            .await;
+        assert!(matches!(result, Err(JakartaError::KeyCollision(key)) if key == "a"));
+
+        let result = jakarta
+            .interpolate_map_with_keys(colliding, OnKeyCollision::LastWins)
+            .await
+            .unwrap();
+        assert_eq!(result, map(&[("a", "2"), ("b", "3")]));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_reference_cycles() {
+        let jakarta = Jakarta::new(HashMap::new()).unwrap();
+
+        let result = jakarta
+            .interpolate_map(map(&[("a", "${b}"), ("b", "x${c}"), ("c", "${a}")]))
+            .await;
+
+        match result {
+            Err(JakartaError::ReferenceCycle(cycle)) => {
+                assert_eq!(cycle, vec!["a", "b", "c", "a"]);
+            }
+            other => panic!("expected a reference cycle, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::time::Duration;
+
+/// Receives counters and timings from an interpolation, e.g. to feed a Prometheus exporter.
+/// Every callback defaults to a no-op.
+pub trait MetricsRecorder: Send + Sync {
+    /// Called once per interpolated string.
+    fn record_interpolation(&self) {}
+
+    /// Called after `command` was run, with the time it took to resolve.
+    fn record_invocation(&self, _command: &str, _elapsed: Duration) {}
+
+    /// Called when a placeholder was served from the per-call cache instead of running `command`.
+    fn record_cache_hit(&self, _command: &str) {}
+
+    /// Called when `command` isn't registered or failed to resolve. Placeholders that merely used
+    /// their default value because the command returned an empty value don't count.
+    fn record_failure(&self, _command: &str) {}
+}
+
+/// Forwards to the global recorder of the `metrics` crate, labelling everything by `command`.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MetricsCrateRecorder;
+
+#[cfg(feature = "metrics")]
+impl MetricsRecorder for MetricsCrateRecorder {
+    fn record_interpolation(&self) {
+        metrics::counter!("jakarta_interpolations_total").increment(1);
+    }
+
+    fn record_invocation(&self, command: &str, elapsed: Duration) {
+        let command = command.to_owned();
+        metrics::counter!("jakarta_command_invocations_total", "command" => command.clone())
+            .increment(1);
+        metrics::histogram!("jakarta_command_duration_seconds", "command" => command)
+            .record(elapsed.as_secs_f64());
+    }
+
+    fn record_cache_hit(&self, command: &str) {
+        metrics::counter!("jakarta_cache_hits_total", "command" => command.to_owned()).increment(1);
+    }
+
+    fn record_failure(&self, command: &str) {
+        metrics::counter!("jakarta_command_failures_total", "command" => command.to_owned())
+            .increment(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex as StdMutex},
+    };
+
+    use async_trait::async_trait;
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::{CommandError, Context, JakartaBuilder, JakartaCommand};
+
+    #[derive(Default)]
+    struct MockRecorder {
+        interpolations: StdMutex<usize>,
+        invocations: StdMutex<HashMap<String, usize>>,
+        cache_hits: StdMutex<usize>,
+        failures: StdMutex<Vec<String>>,
+    }
+
+    impl MetricsRecorder for MockRecorder {
+        fn record_interpolation(&self) {
+            *self.interpolations.lock().unwrap() += 1;
+        }
+
+        fn record_invocation(&self, command: &str, _: Duration) {
+            *self
+                .invocations
+                .lock()
+                .unwrap()
+                .entry(command.to_owned())
+                .or_default() += 1;
+        }
+
+        fn record_cache_hit(&self, _: &str) {
+            *self.cache_hits.lock().unwrap() += 1;
+        }
+
+        fn record_failure(&self, command: &str) {
+            self.failures.lock().unwrap().push(command.to_owned());
+        }
+    }
+
+    struct EchoCommand {}
+
+    #[async_trait]
+    impl JakartaCommand for EchoCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            default_value: Option<String>,
+        ) -> String {
+            match args.as_str() {
+                "missing" => default_value.unwrap_or_default(),
+                _ => args,
+            }
+        }
+
+        async fn try_process(
+            &mut self,
+            _: &Context,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> Result<String, CommandError> {
+            match args.as_str() {
+                "missing" => Ok("".to_owned()),
+                "broken" => Err(CommandError("broken".to_owned())),
+                _ => Ok(args),
+            }
+        }
+
+        fn cacheable(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn it_records_metrics() {
+        let recorder = Arc::new(MockRecorder::default());
+        let jakarta = JakartaBuilder::new()
+            .command("echo", Arc::new(Mutex::new(EchoCommand {})))
+            .metrics_recorder(recorder.clone())
+            .build()
+            .unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                "${echo:a} ${echo:a} ${echo:b} ${echo:missing:-x} ${echo:broken:-y} ${unknown:c}"
+                    .to_owned(),
+            )
+            .await;
+        assert_eq!(result, "a a b x y ".to_owned());
+
+        jakarta.interpolate_string("${echo:a}".to_owned()).await;
+
+        assert_eq!(*recorder.interpolations.lock().unwrap(), 2);
+        assert_eq!(
+            *recorder.invocations.lock().unwrap(),
+            HashMap::from([("echo".to_owned(), 5)])
+        );
+        assert_eq!(*recorder.cache_hits.lock().unwrap(), 1);
+        assert_eq!(
+            *recorder.failures.lock().unwrap(),
+            vec!["echo".to_owned(), "unknown".to_owned()]
+        );
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn it_forwards_to_the_metrics_crate() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let jakarta = JakartaBuilder::new()
+            .command("echo", Arc::new(Mutex::new(EchoCommand {})))
+            .metrics_recorder(Arc::new(MetricsCrateRecorder))
+            .build()
+            .unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let result = metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(jakarta.interpolate_string(
+                "${echo:a} ${echo:a} ${echo:missing:-x} ${echo:broken:-y}".to_owned(),
+            ))
+        });
+        assert_eq!(result, "a a x y".to_owned());
+
+        let counters = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter_map(|(key, _, _, value)| match value {
+                DebugValue::Counter(value) => Some((key.key().name().to_owned(), value)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(
+            counters,
+            HashMap::from([
+                ("jakarta_interpolations_total".to_owned(), 1),
+                ("jakarta_command_invocations_total".to_owned(), 3),
+                ("jakarta_cache_hits_total".to_owned(), 1),
+                ("jakarta_command_failures_total".to_owned(), 1),
+            ])
+        );
+    }
+}
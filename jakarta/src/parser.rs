@@ -0,0 +1,923 @@
+use std::{borrow::Cow, ops::Range};
+
+use crate::{
+    filters::PIPE,
+    jakarta::{JakartaError, DEFAULT_CLOSING_DELIMITER, DEFAULT_OPENING_DELIMITER},
+};
+
+const DEFAULT_SEPARATOR: &str = ",";
+
+/// Operators that unquoted args can contain literally when escaped, e.g. `${sh:echo a\:=b}` or
+/// `${sh:echo a \| tr a b}`.
+const ESCAPED_OPERATORS: [&str; 4] = ["\\:-", "\\:=", "\\:?", "\\|"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Placeholder<'s> {
+    pub span: Range<usize>,
+    pub excluded: bool,
+    pub command: Option<&'s str>,
+    pub args: Option<Cow<'s, str>>,
+    pub quoted_args: bool,
+    /// The join separator of a `${cmd:args[*]:separator}` list placeholder.
+    pub separator: Option<&'s str>,
+    pub field: Option<&'s str>,
+    pub default_value: Option<&'s str>,
+    /// Whether the default replaces empty values too (`:-`), or only values the command couldn't
+    /// resolve (`:=`). Unlike POSIX, where `:=` also assigns empty values and `-` is the unset-only
+    /// form, `:=` never replaces a value the command resolved. Args containing a literal `:-`, `:=`
+    /// or `:?` need to be quoted or escaped, e.g. `${sh:echo a\:=b}`.
+    pub default_if_empty: bool,
+    /// The `then` and `else` values of a `${cmd:args:?then:else}` placeholder. Either may be
+    /// quoted to contain `:` or `|`, e.g. `${env:TLS:?"https://host:443":"http://host:80"}`.
+    /// Unlike POSIX, `:?` is a conditional rather than an error when the value is unset.
+    pub branches: Option<(Cow<'s, str>, Cow<'s, str>)>,
+    pub filters: Option<&'s str>,
+    /// Set by a `!` directly after the command, args or field of a placeholder without a default
+    /// or branches, e.g. `${env:KEY!}`. Anywhere else, or escaped as `\!`, it's a literal `!`.
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedPlaceholder {
+    pub command: String,
+    pub args: Option<String>,
+    pub field: Option<String>,
+    pub default_value: Option<String>,
+    pub default_if_empty: bool,
+    pub filters: Option<String>,
+    pub required: bool,
+}
+
+/// Parses `input` as a single `${command:args#field:-default | filters}` placeholder, the same way
+/// interpolation does. Returns `None` if `input` isn't exactly one placeholder.
+pub fn parse_placeholder(input: &str) -> Option<ParsedPlaceholder> {
+    let parser = Parser::new(DEFAULT_OPENING_DELIMITER, DEFAULT_CLOSING_DELIMITER).ok()?;
+
+    match parser.placeholders(input).as_slice() {
+        [placeholder] if placeholder.span == (0..input.len()) && !placeholder.excluded => {
+            Some(ParsedPlaceholder {
+                command: placeholder.command?.to_owned(),
+                args: placeholder.args.as_deref().map(str::to_owned),
+                field: placeholder.field.map(str::to_owned),
+                default_value: placeholder.default_value.map(str::to_owned),
+                default_if_empty: placeholder.default_if_empty,
+                filters: placeholder.filters.map(str::to_owned),
+                required: placeholder.required,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Parser {
+    opening: String,
+    closing: String,
+    excluded_opening: String,
+    bare_opening: String,
+}
+
+impl Parser {
+    pub fn new(opening: &str, closing: &str) -> Result<Self, JakartaError> {
+        let prefix = match (opening.chars().next(), closing.is_empty()) {
+            (Some(prefix), false) => prefix,
+            _ => {
+                return Err(JakartaError::InvalidDelimiters(
+                    opening.to_owned(),
+                    closing.to_owned(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            opening: opening.to_owned(),
+            closing: closing.to_owned(),
+            excluded_opening: format!("{prefix}{opening}"),
+            bare_opening: opening[prefix.len_utf8()..].to_owned(),
+        })
+    }
+
+    pub fn opening(&self) -> &str {
+        &self.opening
+    }
+
+    pub fn excluded_opening(&self) -> &str {
+        &self.excluded_opening
+    }
+
+    pub fn contains_placeholder(&self, input: &str) -> bool {
+        self.next_placeholder(input, 0, &mut vec![]).is_some()
+    }
+
+    pub fn placeholders<'s>(&self, input: &'s str) -> Vec<Placeholder<'s>> {
+        let mut placeholders = vec![];
+        let mut idx = 0;
+        let mut unterminated = vec![];
+
+        while let Some(placeholder) = self.next_placeholder(input, idx, &mut unterminated) {
+            idx = placeholder.span.end;
+            placeholders.push(placeholder);
+        }
+
+        placeholders
+    }
+
+    /// Returns the end of the placeholder whose body starts at `start`, skipping over quoted args.
+    pub fn placeholder_end(&self, input: &str, start: usize) -> Option<usize> {
+        let body_start = match self.quoted_args(&input[start..]) {
+            Some(quoted_args) => start + quoted_args.end,
+            None => start,
+        };
+
+        self.closing_end(input, body_start)
+    }
+
+    /// Returns the end of the placeholder body starting at `start`, i.e. the index just past the
+    /// closing delimiter that balances it, skipping over nested placeholders and bare braces.
+    pub fn closing_end(&self, input: &str, start: usize) -> Option<usize> {
+        self.balance(input, start).ok()
+    }
+
+    /// Like `closing_end`, but if the body is never closed, returns the body starts of the opening
+    /// delimiters nested in it that are left unclosed as well, in ascending order.
+    fn balance(&self, input: &str, start: usize) -> Result<usize, Vec<usize>> {
+        let mut unclosed = vec![];
+        let mut idx = start;
+
+        while let Some(rest) = input.get(idx..).filter(|rest| !rest.is_empty()) {
+            if rest.starts_with(&self.opening) {
+                idx += self.opening.len();
+                unclosed.push(idx);
+            } else if !self.bare_opening.is_empty() && rest.starts_with(&self.bare_opening) {
+                idx += self.bare_opening.len();
+                unclosed.push(idx);
+            } else if rest.starts_with(&self.closing) {
+                idx += self.closing.len();
+
+                if unclosed.pop().is_none() {
+                    return Ok(idx);
+                }
+            } else {
+                idx += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+
+        Err(unclosed)
+    }
+
+    /// Describes the first opening delimiter in `input` that doesn't start a valid placeholder,
+    /// e.g. because it is never closed. Escaped openings are ignored.
+    pub fn first_invalid(&self, input: &str) -> Option<(Range<usize>, &'static str)> {
+        let mut idx = 0;
+
+        while let Some(rest) = input.get(idx..).filter(|rest| !rest.is_empty()) {
+            if rest.starts_with(&self.excluded_opening) {
+                idx += self.excluded_opening.len();
+                continue;
+            }
+            if !rest.starts_with(&self.opening) {
+                idx += rest.chars().next().map_or(1, char::len_utf8);
+                continue;
+            }
+
+            let body_start = idx + self.opening.len();
+            let Some(end) = self.placeholder_end(input, body_start) else {
+                return Some((idx..body_start, "unterminated placeholder"));
+            };
+            if self
+                .parse_body(
+                    &input[body_start..end - self.closing.len()],
+                    idx..end,
+                    false,
+                )
+                .is_none()
+            {
+                return Some((idx..end, "invalid placeholder"));
+            }
+
+            idx = end;
+        }
+
+        None
+    }
+
+    /// Returns the first placeholder starting at or after `from`. `unterminated` holds the body
+    /// starts known to never be closed, so unclosed openings aren't scanned to the end of `input`
+    /// over and over.
+    fn next_placeholder<'s>(
+        &self,
+        input: &'s str,
+        from: usize,
+        unterminated: &mut Vec<usize>,
+    ) -> Option<Placeholder<'s>> {
+        let mut idx = from;
+
+        while let Some(rest) = input.get(idx..).filter(|rest| !rest.is_empty()) {
+            let (excluded, body_start) = if rest.starts_with(&self.excluded_opening) {
+                (true, idx + self.excluded_opening.len())
+            } else if rest.starts_with(&self.opening) {
+                (false, idx + self.opening.len())
+            } else {
+                idx += rest.chars().next().map_or(1, char::len_utf8);
+                continue;
+            };
+
+            let end = match self.quoted_args(&input[body_start..]) {
+                Some(quoted_args) => self.closing_end(input, body_start + quoted_args.end),
+                None if unterminated.binary_search(&body_start).is_ok() => None,
+                None => self
+                    .balance(input, body_start)
+                    .map_err(|unclosed| {
+                        *unterminated = unclosed;
+                    })
+                    .ok(),
+            };
+
+            if let Some(end) = end {
+                let body = &input[body_start..end - self.closing.len()];
+
+                if let Some(placeholder) = self.parse_body(body, idx..end, excluded) {
+                    return Some(placeholder);
+                }
+            }
+
+            idx += rest.chars().next().map_or(1, char::len_utf8);
+        }
+
+        None
+    }
+
+    fn parse_body<'s>(
+        &self,
+        body: &'s str,
+        span: Range<usize>,
+        excluded: bool,
+    ) -> Option<Placeholder<'s>> {
+        let quoted_args = self.quoted_args(body);
+        let top_level = self.top_level_mask(body, quoted_args.clone());
+        let mut cursor = Cursor {
+            body,
+            top_level: &top_level,
+            idx: 0,
+        };
+
+        let mut placeholder = Placeholder {
+            span,
+            excluded,
+            command: None,
+            args: None,
+            quoted_args: false,
+            separator: None,
+            field: None,
+            default_value: None,
+            default_if_empty: true,
+            branches: None,
+            filters: None,
+            required: false,
+        };
+
+        cursor.skip_whitespace();
+        if cursor.is_done() {
+            return Some(placeholder);
+        }
+
+        let command = cursor.take_while(|c| {
+            c != ':' && c != '|' && !c.is_whitespace() && !self.is_delimiter_char(c)
+        });
+        cursor.skip_whitespace();
+        let command = match strip_required_marker(command) {
+            Some(command) if cursor.ends_segments() => {
+                placeholder.required = true;
+                command
+            }
+            _ => command,
+        };
+        if command.is_empty() {
+            return None;
+        }
+        placeholder.command = Some(command);
+
+        if cursor.starts_with(":")
+            && !cursor.starts_with(":-")
+            && !cursor.starts_with(":=")
+            && !cursor.starts_with(":?")
+        {
+            cursor.idx += 1;
+            cursor.skip_whitespace();
+
+            if cursor.starts_with("-") || cursor.starts_with("#") || cursor.starts_with(PIPE) {
+                return None;
+            }
+
+            match quoted_args {
+                Some(quoted_args) if quoted_args.start == cursor.idx => {
+                    placeholder.args = Some(unquote(&body[quoted_args.clone()]));
+                    placeholder.quoted_args = true;
+
+                    cursor.idx = quoted_args.end;
+                    cursor.skip_whitespace();
+                }
+                _ => {
+                    let args = cursor
+                        .take_until_top_level(&["[*]", "#", ":-", ":=", ":?", PIPE])
+                        .trim_end();
+                    let args = match args.strip_suffix("\\!") {
+                        Some(escaped) => Cow::Owned(format!("{}!", unescape_operators(escaped))),
+                        None => match strip_required_marker(args) {
+                            Some(args) if cursor.ends_segments() => {
+                                placeholder.required = true;
+                                unescape_operators(args)
+                            }
+                            _ => unescape_operators(args),
+                        },
+                    };
+                    if args.is_empty() {
+                        return None;
+                    }
+                    placeholder.args = Some(args);
+                }
+            }
+
+            if cursor.starts_with("[*]") {
+                cursor.idx += 3;
+
+                let separator = if cursor.starts_with(":")
+                    && !cursor.starts_with(":-")
+                    && !cursor.starts_with(":=")
+                    && !cursor.starts_with(":?")
+                {
+                    cursor.idx += 1;
+                    cursor.take_until_top_level(&["#", ":-", ":=", ":?", PIPE])
+                } else {
+                    DEFAULT_SEPARATOR
+                };
+                placeholder.separator = Some(separator);
+            }
+
+            if cursor.starts_with("#") {
+                cursor.idx += 1;
+                cursor.skip_whitespace();
+
+                let mut field = cursor
+                    .take_until_top_level(&[":-", ":=", ":?", PIPE])
+                    .trim_end();
+                if let Some(stripped) =
+                    strip_required_marker(field).filter(|_| cursor.ends_segments())
+                {
+                    placeholder.required = true;
+                    field = stripped;
+                }
+                if field.is_empty() {
+                    return None;
+                }
+                placeholder.field = Some(field);
+            }
+
+            cursor.skip_whitespace();
+        }
+
+        if cursor.starts_with("!") {
+            cursor.idx += 1;
+            cursor.skip_whitespace();
+            if !cursor.ends_segments() {
+                return None;
+            }
+            placeholder.required = true;
+        }
+
+        if cursor.starts_with(":-") || cursor.starts_with(":=") {
+            placeholder.default_if_empty = cursor.starts_with(":-");
+            cursor.idx += 2;
+            cursor.skip_whitespace();
+
+            let default_value = cursor.take_until_top_level(&[PIPE]).trim_end();
+            if default_value.is_empty() {
+                return None;
+            }
+            placeholder.default_value = Some(default_value);
+
+            cursor.skip_whitespace();
+        } else if cursor.starts_with(":?") {
+            cursor.idx += 2;
+            cursor.skip_whitespace();
+
+            let then_value = cursor.take_branch(&[":", PIPE])?;
+            let else_value = if cursor.starts_with(":") {
+                cursor.idx += 1;
+                cursor.skip_whitespace();
+                cursor.take_branch(&[PIPE])?
+            } else {
+                Cow::Borrowed("")
+            };
+            placeholder.branches = Some((then_value, else_value));
+
+            cursor.skip_whitespace();
+        }
+
+        if cursor.starts_with(PIPE) {
+            cursor.idx += PIPE.len();
+
+            let filters = cursor.take_until_top_level(&[]).trim();
+            if filters.is_empty() {
+                return None;
+            }
+            placeholder.filters = Some(filters);
+        }
+
+        cursor.skip_whitespace();
+        cursor.is_done().then_some(placeholder)
+    }
+
+    fn is_delimiter_char(&self, c: char) -> bool {
+        self.bare_opening.contains(c) || self.closing.contains(c)
+    }
+
+    /// Returns the range of a quoted args segment (`${cmd:"..."}`), including both quotes.
+    fn quoted_args(&self, body: &str) -> Option<Range<usize>> {
+        let mut cursor = Cursor {
+            body,
+            top_level: &[],
+            idx: 0,
+        };
+
+        cursor.skip_whitespace();
+        cursor.take_while(|c| {
+            c != ':' && c != '|' && !c.is_whitespace() && !self.is_delimiter_char(c)
+        });
+        cursor.skip_whitespace();
+
+        let rest = cursor.rest();
+        if !rest.starts_with(':')
+            || rest.starts_with(":-")
+            || rest.starts_with(":=")
+            || rest.starts_with(":?")
+        {
+            return None;
+        }
+        cursor.idx += 1;
+        cursor.skip_whitespace();
+
+        let start = cursor.idx;
+        quoted_len(cursor.rest()).map(|len| start..start + len)
+    }
+
+    fn top_level_mask(&self, body: &str, quoted_args: Option<Range<usize>>) -> Vec<bool> {
+        let mut mask = vec![true; body.len()];
+        let mut depth = 0usize;
+        let mut idx = 0;
+
+        while let Some(rest) = body.get(idx..).filter(|rest| !rest.is_empty()) {
+            let (len, next_depth) = if let Some(quoted_args) = quoted_args
+                .as_ref()
+                .filter(|quoted_args| quoted_args.start == idx)
+            {
+                (quoted_args.len(), depth + 1)
+            } else if rest.starts_with(&self.opening) {
+                depth += 1;
+                (self.opening.len(), depth)
+            } else if !self.bare_opening.is_empty() && rest.starts_with(&self.bare_opening) {
+                depth += 1;
+                (self.bare_opening.len(), depth)
+            } else if depth > 0 && rest.starts_with(&self.closing) {
+                depth -= 1;
+                (self.closing.len(), depth + 1)
+            } else {
+                (rest.chars().next().map_or(1, char::len_utf8), depth)
+            };
+
+            mask[idx..idx + len].fill(next_depth == 0);
+            idx += len;
+        }
+
+        mask
+    }
+}
+
+/// Returns the length of the quoted string `rest` starts with, including both quotes.
+fn quoted_len(rest: &str) -> Option<usize> {
+    let mut chars = rest.strip_prefix('"')?.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' => return Some(idx + 2),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Returns `segment` without its trailing `!` required marker, if it has one.
+fn strip_required_marker(segment: &str) -> Option<&str> {
+    segment
+        .strip_suffix('!')
+        .filter(|stripped| !stripped.ends_with('\\'))
+        .map(str::trim_end)
+}
+
+/// Turns the escaped `\:-`, `\:=`, `\:?` and `\|` operators of unquoted args into literal ones.
+fn unescape_operators(args: &str) -> Cow<'_, str> {
+    if !ESCAPED_OPERATORS
+        .iter()
+        .any(|operator| args.contains(operator))
+    {
+        return Cow::Borrowed(args);
+    }
+
+    Cow::Owned(
+        ESCAPED_OPERATORS
+            .iter()
+            .fold(args.to_owned(), |args, operator| {
+                args.replace(operator, &operator[1..])
+            }),
+    )
+}
+
+fn unquote(quoted: &str) -> Cow<'_, str> {
+    let inner = &quoted[1..quoted.len() - 1];
+    if !inner.contains('\\') {
+        return Cow::Borrowed(inner);
+    }
+
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(escaped @ ('"' | '\\'))) => {
+                unquoted.push(escaped);
+                chars.next();
+            }
+            (c, _) => unquoted.push(c),
+        }
+    }
+
+    Cow::Owned(unquoted)
+}
+
+struct Cursor<'s, 'm> {
+    body: &'s str,
+    top_level: &'m [bool],
+    idx: usize,
+}
+
+impl<'s> Cursor<'s, '_> {
+    fn is_done(&self) -> bool {
+        self.idx >= self.body.len()
+    }
+
+    fn rest(&self) -> &'s str {
+        &self.body[self.idx..]
+    }
+
+    fn starts_with(&self, token: &str) -> bool {
+        self.top_level.get(self.idx).copied().unwrap_or(false) && self.rest().starts_with(token)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.idx += rest.len() - rest.trim_start().len();
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'s str {
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .find(|(_, c)| !predicate(*c))
+            .map_or(rest.len(), |(idx, _)| idx);
+
+        self.idx += len;
+        &rest[..len]
+    }
+
+    /// Takes a `:?` branch up to the next top-level token, unquoting a quoted branch. Returns
+    /// `None` if anything but one of `tokens` follows a quoted branch.
+    fn take_branch(&mut self, tokens: &[&str]) -> Option<Cow<'s, str>> {
+        let quoted_len = match quoted_len(self.rest()).filter(|_| self.starts_with("\"")) {
+            Some(quoted_len) => quoted_len,
+            None => return Some(Cow::Borrowed(self.take_until_top_level(tokens).trim_end())),
+        };
+
+        let quoted = &self.rest()[..quoted_len];
+        self.idx += quoted_len;
+        self.skip_whitespace();
+
+        (self.is_done() || tokens.iter().any(|token| self.starts_with(token)))
+            .then(|| unquote(quoted))
+    }
+
+    /// Whether only the filters are left, the only part allowed after a `!` required marker.
+    fn ends_segments(&self) -> bool {
+        self.is_done() || self.starts_with(PIPE)
+    }
+
+    fn take_until_top_level(&mut self, tokens: &[&str]) -> &'s str {
+        let start = self.idx;
+
+        while !self.is_done() && !tokens.iter().any(|token| self.starts_with(token)) {
+            self.idx += match ESCAPED_OPERATORS
+                .iter()
+                .find(|operator| self.rest().starts_with(**operator))
+            {
+                Some(operator) => operator.len(),
+                None => self.rest().chars().next().map_or(1, char::len_utf8),
+            };
+        }
+
+        &self.body[start..self.idx]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Vec<Placeholder<'_>> {
+        Parser::new("${", "}").unwrap().placeholders(input)
+    }
+
+    fn branches<'p>(placeholder: &'p Placeholder) -> Option<(&'p str, &'p str)> {
+        placeholder
+            .branches
+            .as_ref()
+            .map(|(then_value, else_value)| (then_value.as_ref(), else_value.as_ref()))
+    }
+
+    #[test]
+    fn it_parses_all_parts() {
+        let placeholders = parse("a ${ json : {\"a\":{\"b\":1}} #a.b :- {} | trim|upper } b");
+
+        assert_eq!(
+            placeholders,
+            vec![Placeholder {
+                span: 2..51,
+                excluded: false,
+                command: Some("json"),
+                args: Some("{\"a\":{\"b\":1}}".into()),
+                quoted_args: false,
+                separator: None,
+                field: Some("a.b"),
+                default_value: Some("{}"),
+                default_if_empty: true,
+                branches: None,
+                filters: Some("trim|upper"),
+                required: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn it_keeps_escaped_and_quoted_pipes_in_args() {
+        let placeholders =
+            parse(r#"${sh:echo a \| tr a b} ${sh:"echo a|tr a b" | upper|trim} ${sh:echo a | tr}"#);
+
+        assert_eq!(placeholders.len(), 3);
+        assert_eq!(placeholders[0].args.as_deref(), Some("echo a | tr a b"));
+        assert_eq!(placeholders[0].filters, None);
+        assert_eq!(placeholders[1].args.as_deref(), Some("echo a|tr a b"));
+        assert_eq!(placeholders[1].filters, Some("upper|trim"));
+        assert_eq!(placeholders[2].args.as_deref(), Some("echo a"));
+        assert_eq!(placeholders[2].filters, Some("tr"));
+    }
+
+    #[test]
+    fn it_balances_nested_placeholders_and_braces() {
+        let placeholders = parse("${env:VAR_${env:VAR_1}} $${test:{}} ${sh:echo }");
+
+        assert_eq!(placeholders.len(), 3);
+        assert_eq!(placeholders[0].args.as_deref(), Some("VAR_${env:VAR_1}"));
+        assert!(placeholders[1].excluded);
+        assert_eq!(placeholders[1].args.as_deref(), Some("{}"));
+        assert_eq!(placeholders[2].args.as_deref(), Some("echo"));
+    }
+
+    #[test]
+    fn it_skips_malformed_placeholders() {
+        assert_eq!(
+            parse("${cmd:} ${cmd: -x} ${foo bar} ${cmd|} ${a:{}").len(),
+            0
+        );
+
+        let placeholders = parse("${a:{} ${b}");
+        assert_eq!(placeholders.len(), 1);
+        assert_eq!(placeholders[0].command, Some("b"));
+    }
+
+    #[test]
+    fn it_skips_unclosed_placeholders_in_linear_time() {
+        let input = format!("{}${{b}} ${{c", "${a:".repeat(50_000));
+
+        let started = std::time::Instant::now();
+        let placeholders = parse(&input);
+
+        assert_eq!(placeholders.len(), 1);
+        assert_eq!(placeholders[0].command, Some("b"));
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn it_finds_the_closing_delimiter() {
+        let parser = Parser::new("<<", ">>").unwrap();
+
+        assert_eq!(parser.closing_end("<<a:<<b>>>> c", 2), Some(11));
+        assert_eq!(parser.closing_end("<<a:<<b>>", 2), None);
+    }
+
+    #[test]
+    fn it_parses_quoted_args() {
+        let placeholders =
+            parse(r#"${sh:"echo {\"k\":\"v\"} | tr -d }" #0 :- {} | trim} ${sh: "\\" }"#);
+
+        assert_eq!(placeholders.len(), 2);
+        assert_eq!(
+            placeholders[0].args.as_deref(),
+            Some(r#"echo {"k":"v"} | tr -d }"#)
+        );
+        assert!(placeholders[0].quoted_args);
+        assert_eq!(placeholders[0].field, Some("0"));
+        assert_eq!(placeholders[0].default_value, Some("{}"));
+        assert_eq!(placeholders[0].filters, Some("trim"));
+        assert_eq!(placeholders[1].args.as_deref(), Some(r"\"));
+
+        assert_eq!(parse(r#"${sh:"a" b}"#).len(), 0);
+        assert_eq!(
+            parse(r#"${sh:"unterminated}"#)[0].args.as_deref(),
+            Some(r#""unterminated"#)
+        );
+        assert!(!parse(r#"${sh:"unterminated}"#)[0].quoted_args);
+    }
+
+    #[test]
+    fn it_parses_required_markers() {
+        let placeholders = parse(
+            r#"${env:REQUIRED!} ${env:A#b! | upper} ${sh:"echo hi!"} ${!} ${sh:"echo"!} ${env !}"#,
+        );
+
+        assert_eq!(placeholders.len(), 5);
+        assert!(placeholders[0].required);
+        assert_eq!(placeholders[0].args.as_deref(), Some("REQUIRED"));
+        assert!(placeholders[1].required);
+        assert_eq!(placeholders[1].field, Some("b"));
+        assert_eq!(placeholders[1].filters, Some("upper"));
+        assert!(!placeholders[2].required);
+        assert_eq!(placeholders[2].args.as_deref(), Some("echo hi!"));
+        assert!(placeholders[3].required);
+        assert_eq!(placeholders[3].args.as_deref(), Some("echo"));
+        assert!(placeholders[4].required);
+        assert_eq!(placeholders[4].command, Some("env"));
+    }
+
+    #[test]
+    fn it_keeps_literal_exclamation_marks() {
+        let placeholders = parse(
+            r#"${sh:echo hi\!} ${sh:echo hi!:-default} ${env:A:-hello!} ${env:A:?yes!:no!} ${env:A | upper!}"#,
+        );
+
+        assert_eq!(placeholders.len(), 5);
+        assert!(placeholders.iter().all(|placeholder| !placeholder.required));
+        assert_eq!(placeholders[0].args.as_deref(), Some("echo hi!"));
+        assert_eq!(placeholders[1].args.as_deref(), Some("echo hi!"));
+        assert_eq!(placeholders[1].default_value, Some("default"));
+        assert_eq!(placeholders[2].default_value, Some("hello!"));
+        assert_eq!(branches(&placeholders[3]), Some(("yes!", "no!")));
+        assert_eq!(placeholders[4].filters, Some("upper!"));
+    }
+
+    #[test]
+    fn it_keeps_escaped_operators_in_args() {
+        let placeholders = parse(
+            r#"${sh:echo a:=b} ${sh:echo a\:=b} ${sh:echo a\:-b\:?c:-x} ${sh:echo a\:=b\!} ${sh:"echo a:=b"}"#,
+        );
+
+        assert_eq!(placeholders.len(), 5);
+        assert_eq!(placeholders[0].args.as_deref(), Some("echo a"));
+        assert_eq!(placeholders[0].default_value, Some("b"));
+        assert_eq!(placeholders[1].args.as_deref(), Some("echo a:=b"));
+        assert_eq!(placeholders[1].default_value, None);
+        assert_eq!(placeholders[2].args.as_deref(), Some("echo a:-b:?c"));
+        assert_eq!(placeholders[2].default_value, Some("x"));
+        assert_eq!(placeholders[3].args.as_deref(), Some("echo a:=b!"));
+        assert_eq!(placeholders[4].args.as_deref(), Some("echo a:=b"));
+    }
+
+    #[test]
+    fn it_parses_conditional_branches() {
+        let placeholders = parse(
+            "${env:FEATURE:?enabled:disabled} ${env:FEATURE#0:?${env:ON}:${env:OFF}|upper} ${env:FEATURE:?on} ${env:?a:b}",
+        );
+
+        assert_eq!(placeholders.len(), 4);
+        assert_eq!(placeholders[0].args.as_deref(), Some("FEATURE"));
+        assert_eq!(branches(&placeholders[0]), Some(("enabled", "disabled")));
+        assert_eq!(placeholders[1].field, Some("0"));
+        assert_eq!(
+            branches(&placeholders[1]),
+            Some(("${env:ON}", "${env:OFF}"))
+        );
+        assert_eq!(placeholders[1].filters, Some("upper"));
+        assert_eq!(branches(&placeholders[2]), Some(("on", "")));
+        assert_eq!(placeholders[3].args, None);
+        assert_eq!(branches(&placeholders[3]), Some(("a", "b")));
+
+        let placeholders = parse(
+            r#"${env:TLS:?"https://host:443":"http://host:80"} ${env:A:?"a\"|b" | upper} ${env:A:?"a"b}"#,
+        );
+
+        assert_eq!(placeholders.len(), 2);
+        assert_eq!(
+            branches(&placeholders[0]),
+            Some(("https://host:443", "http://host:80"))
+        );
+        assert_eq!(branches(&placeholders[1]), Some((r#"a"|b"#, "")));
+        assert_eq!(placeholders[1].filters, Some("upper"));
+    }
+
+    #[test]
+    fn it_trims_whitespace_around_each_part() {
+        let placeholders = parse(
+            "${ env : FOO # 0 :- fallback value | upper } ${ env : ${ env : INNER } :? yes : no }",
+        );
+
+        assert_eq!(placeholders.len(), 2);
+        assert_eq!(placeholders[0].command, Some("env"));
+        assert_eq!(placeholders[0].args.as_deref(), Some("FOO"));
+        assert_eq!(placeholders[0].field, Some("0"));
+        assert_eq!(placeholders[0].default_value, Some("fallback value"));
+        assert_eq!(placeholders[0].filters, Some("upper"));
+        assert_eq!(placeholders[1].args.as_deref(), Some("${ env : INNER }"));
+        assert_eq!(branches(&placeholders[1]), Some(("yes", "no")));
+
+        let nested = parse(placeholders[1].args.as_deref().unwrap());
+        assert_eq!(nested[0].command, Some("env"));
+        assert_eq!(nested[0].args.as_deref(), Some("INNER"));
+    }
+
+    #[test]
+    fn it_parses_default_operators() {
+        let placeholders = parse("${env:A:-x} ${env:B:=y} ${env:C#0 := z|upper} ${env:D}");
+
+        assert_eq!(placeholders.len(), 4);
+        assert_eq!(placeholders[0].default_value, Some("x"));
+        assert!(placeholders[0].default_if_empty);
+        assert_eq!(placeholders[1].args.as_deref(), Some("B"));
+        assert_eq!(placeholders[1].default_value, Some("y"));
+        assert!(!placeholders[1].default_if_empty);
+        assert_eq!(placeholders[2].field, Some("0"));
+        assert_eq!(placeholders[2].default_value, Some("z"));
+        assert!(!placeholders[2].default_if_empty);
+        assert_eq!(placeholders[2].filters, Some("upper"));
+        assert_eq!(placeholders[3].default_value, None);
+    }
+
+    #[test]
+    fn it_parses_list_separators() {
+        let placeholders =
+            parse("${file:hosts.txt[*]} ${file:hosts.txt[*]:, :-none} ${file:a[*]b}");
+
+        assert_eq!(placeholders.len(), 2);
+        assert_eq!(placeholders[0].args.as_deref(), Some("hosts.txt"));
+        assert_eq!(placeholders[0].separator, Some(","));
+        assert_eq!(placeholders[1].separator, Some(", "));
+        assert_eq!(placeholders[1].default_value, Some("none"));
+    }
+
+    #[test]
+    fn it_parses_public_placeholders() {
+        assert_eq!(
+            parse_placeholder("${env:URL:-postgres://host:5432/db}"),
+            Some(ParsedPlaceholder {
+                command: "env".to_owned(),
+                args: Some("URL".to_owned()),
+                field: None,
+                default_value: Some("postgres://host:5432/db".to_owned()),
+                default_if_empty: true,
+                filters: None,
+                required: false,
+            })
+        );
+        assert_eq!(
+            parse_placeholder("${vault:db/${env:ROLE}#password:-${env:FALLBACK} | trim}"),
+            Some(ParsedPlaceholder {
+                command: "vault".to_owned(),
+                args: Some("db/${env:ROLE}".to_owned()),
+                field: Some("password".to_owned()),
+                default_value: Some("${env:FALLBACK}".to_owned()),
+                default_if_empty: true,
+                filters: Some("trim".to_owned()),
+                required: false,
+            })
+        );
+
+        let placeholder = parse_placeholder(r#"${sh:"printf '}:-#'"!}"#).unwrap();
+        assert_eq!(placeholder.args.as_deref(), Some("printf '}:-#'"));
+        assert_eq!(placeholder.default_value, None);
+        assert!(placeholder.required);
+
+        assert_eq!(parse_placeholder("${env:A} ${env:B}"), None);
+        assert_eq!(parse_placeholder("prefix ${env:A}"), None);
+        assert_eq!(parse_placeholder("$${env:A}"), None);
+        assert_eq!(parse_placeholder("${}"), None);
+        assert_eq!(parse_placeholder("${env:-}"), None);
+    }
+}
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+
+use crate::commands::JakartaCommand;
+
+/// Resolves `${raw:args}` to its trimmed args without running anything, so values that look like
+/// placeholders (`${raw:"${not:expanded}"}`) can be emitted verbatim.
+#[derive(Default)]
+pub struct PassthroughCommand {}
+
+#[async_trait]
+impl JakartaCommand for PassthroughCommand {
+    async fn process(
+        &mut self,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> String {
+        args.trim().to_owned()
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn identifiers(&self) -> Vec<&'static str> {
+        vec!["raw"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::JakartaBuilder;
+
+    #[tokio::test]
+    async fn it_passes_args_through() {
+        let jakarta = JakartaBuilder::new()
+            .register_auto(PassthroughCommand {})
+            .build()
+            .unwrap();
+
+        let result = jakarta
+            .interpolate_string(
+                r#"${raw:hello:world} ${raw: hello:world :-default} ${raw:"${sh:rm -rf /}"}"#
+                    .to_owned(),
+            )
+            .await;
+
+        assert_eq!(result, "hello:world hello:world ${sh:rm -rf /}".to_owned());
+    }
+}
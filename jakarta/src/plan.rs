@@ -0,0 +1,9 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedResolution {
+    pub command: String,
+    pub args: Option<String>,
+    pub field: Option<String>,
+    pub default_value: Option<String>,
+    pub registered: bool,
+    pub conditional: bool,
+}
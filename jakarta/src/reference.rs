@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::{
+    commands::{CommandError, JakartaCommand},
+    context::Context,
+};
+
+pub(crate) const SELF_COMMAND_ID: &str = "self";
+
+/// The entries of an `interpolate_map` batch resolved so far, exposed through the [`Context`].
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedEntries(pub HashMap<String, String>);
+
+/// Resolves `${self:KEY}` to another entry of the map passed to `interpolate_map`. Outside of a
+/// map interpolation, or for unknown keys, it resolves to the default value.
+#[derive(Default)]
+pub struct SelfCommand {}
+
+#[async_trait]
+impl JakartaCommand for SelfCommand {
+    async fn try_process(
+        &mut self,
+        context: &Context,
+        _: String,
+        _: Option<String>,
+        args: String,
+        _: Option<String>,
+        _: Option<String>,
+    ) -> Result<String, CommandError> {
+        context
+            .get::<ResolvedEntries>()
+            .and_then(|entries| entries.0.get(args.trim()))
+            .cloned()
+            .ok_or_else(|| CommandError(format!("No map entry for {args:?}")))
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+
+    fn expand_result(&self) -> bool {
+        false
+    }
+
+    fn identifiers(&self) -> Vec<&'static str> {
+        vec![SELF_COMMAND_ID]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{JakartaBuilder, JakartaError};
+
+    fn map(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn it_resolves_other_map_entries() {
+        let jakarta = JakartaBuilder::new()
+            .register_auto(SelfCommand {})
+            .build()
+            .unwrap();
+
+        let result = jakarta
+            .interpolate_map(map(&[
+                ("endpoint", "https://${self:host}/api"),
+                ("host", "example.com"),
+                ("missing", "${self:nope:-fallback}"),
+            ]))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            map(&[
+                ("endpoint", "https://example.com/api"),
+                ("host", "example.com"),
+                ("missing", "fallback"),
+            ])
+        );
+
+        let result = jakarta.interpolate_string("${self:host}".to_owned()).await;
+        assert_eq!(result, "".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_resolves_references_built_from_nested_placeholders() {
+        let jakarta = JakartaBuilder::new()
+            .register_auto(SelfCommand {})
+            .build()
+            .unwrap();
+
+        let result = jakarta
+            .interpolate_map(map(&[
+                ("endpoint", "https://${self:${self:which}}/api"),
+                ("which", "host"),
+                ("host", "example.com"),
+            ]))
+            .await
+            .unwrap();
+
+        assert_eq!(result["endpoint"], "https://example.com/api".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_rejects_self_reference_cycles() {
+        let jakarta = JakartaBuilder::new()
+            .register_auto(SelfCommand {})
+            .build()
+            .unwrap();
+
+        let result = jakarta
+            .interpolate_map(map(&[("a", "${self:b}"), ("b", "${self:a}")]))
+            .await;
+
+        match result {
+            Err(JakartaError::ReferenceCycle(cycle)) => {
+                assert_eq!(cycle, vec!["a", "b", "a"]);
+            }
+            other => panic!("expected a reference cycle, got {other:?}"),
+        }
+    }
+}
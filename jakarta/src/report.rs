@@ -0,0 +1,158 @@
+use std::{fmt::Write, time::Duration};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceholderReport {
+    pub placeholder: String,
+    pub command: String,
+    pub args: String,
+    pub field: Option<String>,
+    pub value: Option<String>,
+    pub secret: bool,
+    pub elapsed: Duration,
+    pub cached: bool,
+    pub used_default: bool,
+    pub errored: bool,
+}
+
+/// How many placeholders took each path during an interpolation, e.g. to fail CI when values
+/// fall back to their defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InterpolationStats {
+    pub resolved: usize,
+    pub defaulted: usize,
+    pub missing: usize,
+    pub excluded: usize,
+}
+
+/// Something that went wrong while resolving `placeholder`, returned by
+/// `Jakarta::interpolate_collecting`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub placeholder: String,
+    pub reason: WarningReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarningReason {
+    /// No command is registered for the placeholder.
+    UnknownCommand,
+    /// The command or a filter reported a problem through [`crate::Diagnostics`].
+    Reported(String),
+    /// The placeholder silently fell back to its default value.
+    Defaulted,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InterpolationReport {
+    pub placeholders: Vec<PlaceholderReport>,
+}
+
+impl InterpolationReport {
+    pub fn elapsed(&self) -> Duration {
+        self.placeholders
+            .iter()
+            .map(|placeholder| placeholder.elapsed)
+            .sum()
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"elapsed_us\":{},\"placeholders\":[",
+            self.elapsed().as_micros()
+        );
+
+        for (idx, placeholder) in self.placeholders.iter().enumerate() {
+            if idx > 0 {
+                json.push(',');
+            }
+
+            let _ = write!(
+                json,
+                "{{\"placeholder\":{},\"command\":{},\"args\":{},\"field\":{},\"value\":{},\"secret\":{},\"elapsed_us\":{},\"cached\":{},\"used_default\":{},\"errored\":{}}}",
+                json_string(&placeholder.placeholder),
+                json_string(&placeholder.command),
+                json_string(&placeholder.args),
+                placeholder
+                    .field
+                    .as_deref()
+                    .map_or("null".to_owned(), json_string),
+                placeholder
+                    .value
+                    .as_deref()
+                    .map_or("null".to_owned(), json_string),
+                placeholder.secret,
+                placeholder.elapsed.as_micros(),
+                placeholder.cached,
+                placeholder.used_default,
+                placeholder.errored,
+            );
+        }
+
+        json.push_str("]}");
+        json
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_serializes_to_json() {
+        let report = InterpolationReport {
+            placeholders: vec![
+                PlaceholderReport {
+                    placeholder: "${env:HOME}".to_owned(),
+                    command: "env".to_owned(),
+                    args: "HOME".to_owned(),
+                    field: None,
+                    value: Some("/root".to_owned()),
+                    secret: false,
+                    elapsed: Duration::from_micros(1500),
+                    cached: false,
+                    used_default: false,
+                    errored: false,
+                },
+                PlaceholderReport {
+                    placeholder: "${file:\"a\"\n#raw}".to_owned(),
+                    command: "file".to_owned(),
+                    args: "\"a\"\n".to_owned(),
+                    field: Some("raw".to_owned()),
+                    value: None,
+                    secret: true,
+                    elapsed: Duration::from_micros(500),
+                    cached: true,
+                    used_default: true,
+                    errored: true,
+                },
+            ],
+        };
+
+        assert_eq!(
+            report.to_json(),
+            r#"{"elapsed_us":2000,"placeholders":[{"placeholder":"${env:HOME}","command":"env","args":"HOME","field":null,"value":"/root","secret":false,"elapsed_us":1500,"cached":false,"used_default":false,"errored":false},{"placeholder":"${file:\"a\"\n#raw}","command":"file","args":"\"a\"\n","field":"raw","value":null,"secret":true,"elapsed_us":500,"cached":true,"used_default":true,"errored":true}]}"#
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::{
+    commands::{CommandError, JakartaCommand},
+    context::Context,
+};
+
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Retries `inner.try_process` up to `max_retries` times, doubling `backoff` after every failed
+/// attempt up to `max_backoff`, before resolving to the default value. Commands that only
+/// implement `process` can't report failures, so they're never retried.
+pub struct RetryCommand<C> {
+    inner: C,
+    max_retries: usize,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl<C: JakartaCommand> RetryCommand<C> {
+    pub fn new(inner: C, max_retries: usize, backoff: Duration) -> Self {
+        Self {
+            inner,
+            max_retries,
+            backoff,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    /// Caps the delay between attempts, 30 seconds by default.
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+}
+
+#[async_trait]
+impl<C: JakartaCommand> JakartaCommand for RetryCommand<C> {
+    async fn try_process(
+        &mut self,
+        context: &Context,
+        command: String,
+        instance: Option<String>,
+        args: String,
+        field: Option<String>,
+        default_value: Option<String>,
+    ) -> Result<String, CommandError> {
+        let mut backoff = self.backoff.min(self.max_backoff);
+        let mut attempt = 0;
+
+        loop {
+            let result = self
+                .inner
+                .try_process(
+                    context,
+                    command.clone(),
+                    instance.clone(),
+                    args.clone(),
+                    field.clone(),
+                    default_value.clone(),
+                )
+                .await;
+
+            match result {
+                Err(err) if attempt < self.max_retries => {
+                    context.diagnostics().warn(&format!(
+                        "Attempt {} for {args:?} failed: {err}, retrying",
+                        attempt + 1
+                    ));
+
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2).min(self.max_backoff);
+                    attempt += 1;
+                }
+                result => {
+                    return result.map_err(|err| {
+                        CommandError(format!(
+                            "Giving up on {args:?} after {attempt} retries: {err}"
+                        ))
+                    })
+                }
+            }
+        }
+    }
+
+    fn cacheable(&self) -> bool {
+        self.inner.cacheable()
+    }
+
+    fn expand_result(&self) -> bool {
+        self.inner.expand_result()
+    }
+
+    fn is_secret(&self) -> bool {
+        self.inner.is_secret()
+    }
+
+    fn marks_nested_values(&self) -> bool {
+        self.inner.marks_nested_values()
+    }
+
+    fn end_interpolation(&mut self) {
+        self.inner.end_interpolation()
+    }
+
+    fn identifiers(&self) -> Vec<&'static str> {
+        self.inner.identifiers()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::Jakarta;
+
+    struct FlakyCommand {
+        failures: usize,
+        attempts: usize,
+    }
+
+    #[async_trait]
+    impl JakartaCommand for FlakyCommand {
+        async fn process(
+            &mut self,
+            command: String,
+            instance: Option<String>,
+            args: String,
+            field: Option<String>,
+            default_value: Option<String>,
+        ) -> String {
+            self.try_process(&Context::new(), command, instance, args, field, None)
+                .await
+                .unwrap_or_else(|_| default_value.unwrap_or_default())
+        }
+
+        async fn try_process(
+            &mut self,
+            _: &Context,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> Result<String, CommandError> {
+            self.attempts += 1;
+
+            match self.attempts > self.failures {
+                true => Ok(format!("{args}@{}", self.attempts)),
+                false => Err(CommandError("503 Service Unavailable".to_owned())),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn it_retries_failed_attempts() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        commands.insert(
+            "flaky",
+            Arc::new(Mutex::new(RetryCommand::new(
+                FlakyCommand {
+                    failures: 2,
+                    attempts: 0,
+                },
+                2,
+                Duration::from_millis(1),
+            ))),
+        );
+        commands.insert(
+            "flakier",
+            Arc::new(Mutex::new(RetryCommand::new(
+                FlakyCommand {
+                    failures: 2,
+                    attempts: 0,
+                },
+                1,
+                Duration::from_millis(1),
+            ))),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = jakarta
+            .interpolate_string("${flaky:value:-default} ${flakier:value:-default}".to_owned())
+            .await;
+
+        assert_eq!(result, "value@3 default".to_owned());
+    }
+
+    #[tokio::test]
+    async fn it_caps_the_backoff_and_reports_failed_attempts() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        commands.insert(
+            "flaky",
+            Arc::new(Mutex::new(
+                RetryCommand::new(
+                    FlakyCommand {
+                        failures: 3,
+                        attempts: 0,
+                    },
+                    3,
+                    Duration::MAX,
+                )
+                .with_max_backoff(Duration::from_millis(1)),
+            )),
+        );
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let (result, warnings) = tokio::time::timeout(
+            Duration::from_secs(5),
+            jakarta.interpolate_collecting("${flaky:value}".to_owned()),
+        )
+        .await
+        .expect("the backoff was not capped");
+
+        assert_eq!(result, "value@4".to_owned());
+        assert_eq!(warnings.len(), 3);
+    }
+}
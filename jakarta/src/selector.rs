@@ -0,0 +1,119 @@
+/// Post-processing for line based command output, selected via `#line:N` (1-based) or
+/// `#kv:KEY` (the value of a `KEY=VALUE` line).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldSelector {
+    Line(usize),
+    Key(String),
+}
+
+impl FieldSelector {
+    pub fn parse(field: &str) -> Option<Self> {
+        match field.split_once(':')? {
+            ("line", line) => line
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|line| *line > 0)
+                .map(Self::Line),
+            ("kv", key) if !key.trim().is_empty() => Some(Self::Key(key.trim().to_owned())),
+            _ => None,
+        }
+    }
+
+    pub fn select(&self, output: &str) -> Option<String> {
+        match self {
+            Self::Line(line) => output.lines().nth(line - 1).map(str::to_owned),
+            Self::Key(key) => output.lines().find_map(|line| {
+                let (line_key, value) = line.split_once('=')?;
+                (line_key.trim() == key).then(|| value.trim().to_owned())
+            }),
+        }
+    }
+}
+
+/// A `#start:end` field, applied by the core to the resolved value instead of being passed on to
+/// the command. Indices count chars rather than bytes and are clamped to the value's length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Slice {
+    start: Option<usize>,
+    end: Option<usize>,
+}
+
+impl Slice {
+    pub fn parse(field: &str) -> Option<Self> {
+        let (start, end) = field.split_once(':')?;
+        let bound = |bound: &str| match bound.trim() {
+            "" => Some(None),
+            bound if bound.bytes().all(|byte| byte.is_ascii_digit()) => {
+                bound.parse().ok().map(Some)
+            }
+            _ => None,
+        };
+
+        Some(Self {
+            start: bound(start)?,
+            end: bound(end)?,
+        })
+    }
+
+    pub fn apply(&self, value: &str) -> String {
+        let start = self.start.unwrap_or(0);
+        let end = self.end.unwrap_or(usize::MAX);
+
+        value
+            .chars()
+            .skip(start)
+            .take(end.saturating_sub(start))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_slices_by_chars() {
+        let sha = "0123456789abcdef";
+        let slice = |field| Slice::parse(field).unwrap();
+
+        assert_eq!(slice("0:7").apply(sha), "0123456");
+        assert_eq!(slice(":4").apply(sha), "0123");
+        assert_eq!(slice("12:").apply(sha), "cdef");
+        assert_eq!(slice("10:100").apply(sha), "abcdef");
+        assert_eq!(slice("100:").apply(sha), "");
+        assert_eq!(slice("5:2").apply(sha), "");
+        assert_eq!(slice("1:3").apply("héllo wörld"), "él");
+        assert_eq!(slice("6:").apply("héllo wörld"), "wörld");
+        assert_eq!(slice("0:2").apply("🦀🦀🦀"), "🦀🦀");
+
+        assert_eq!(Slice::parse("line:2"), None);
+        assert_eq!(Slice::parse("kv:-1"), None);
+        assert_eq!(Slice::parse("0"), None);
+    }
+
+    #[test]
+    fn it_selects_lines_and_keys() {
+        let output = "HOST=db.internal\nPORT = 5432\n";
+
+        assert_eq!(
+            FieldSelector::parse("line:2").and_then(|selector| selector.select(output)),
+            Some("PORT = 5432".to_owned())
+        );
+        assert_eq!(
+            FieldSelector::parse("kv:PORT").and_then(|selector| selector.select(output)),
+            Some("5432".to_owned())
+        );
+        assert_eq!(
+            FieldSelector::parse("line:3").and_then(|selector| selector.select(output)),
+            None
+        );
+        assert_eq!(
+            FieldSelector::parse("kv:USER").and_then(|selector| selector.select(output)),
+            None
+        );
+        assert_eq!(FieldSelector::parse("line:0"), None);
+        assert_eq!(FieldSelector::parse("kv:"), None);
+        assert_eq!(FieldSelector::parse("raw"), None);
+    }
+}
@@ -0,0 +1,323 @@
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::jakarta::{InterpolationState, Jakarta, JakartaError};
+
+const CHUNK_SIZE: usize = 8192;
+
+/// How much of an unclosed placeholder is buffered while waiting for its closing delimiter. Past
+/// this, the opening delimiter is written out as literal text.
+const MAX_PENDING_LEN: usize = 64 * 1024;
+
+enum Segment {
+    Text(usize),
+    Placeholder(usize),
+    Incomplete,
+}
+
+impl<'a> Jakarta<'a> {
+    /// Interpolates `reader` into `writer` in a single pass, only buffering the placeholder that
+    /// is currently being read. Placeholders longer than 64 KiB are treated as literal text.
+    ///
+    /// The whole stream is one interpolation, so cacheable commands resolve repeated
+    /// placeholders once.
+    pub async fn interpolate_reader<R, W>(
+        &self,
+        mut reader: R,
+        mut writer: W,
+    ) -> Result<(), JakartaError>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut state = InterpolationState::default();
+        self.begin_session(&mut state);
+
+        let result = self.stream(&mut reader, &mut writer, &mut state).await;
+        self.end_session().await;
+
+        result
+    }
+
+    async fn stream<R, W>(
+        &self,
+        reader: &mut R,
+        writer: &mut W,
+        state: &mut InterpolationState,
+    ) -> Result<(), JakartaError>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut chunk = vec![0u8; CHUNK_SIZE];
+        let mut undecoded = vec![];
+        let mut pending = String::new();
+
+        loop {
+            let read = reader.read(&mut chunk).await?;
+            let eof = read == 0;
+
+            undecoded.extend_from_slice(&chunk[..read]);
+            decode_utf8(&mut undecoded, &mut pending, eof)?;
+
+            while !pending.is_empty() {
+                match self.next_segment(&pending, eof) {
+                    Segment::Text(end) => {
+                        writer.write_all(&pending.as_bytes()[..end]).await?;
+                        pending.drain(..end);
+                    }
+                    Segment::Placeholder(end) => {
+                        let placeholder = pending.drain(..end).collect::<String>();
+                        let interpolated_string =
+                            self.interpolate_segment(placeholder, state).await;
+
+                        writer.write_all(interpolated_string.as_bytes()).await?;
+                    }
+                    Segment::Incomplete => break,
+                }
+            }
+
+            if eof {
+                break;
+            }
+        }
+
+        writer.flush().await?;
+
+        Ok(())
+    }
+
+    fn next_segment(&self, pending: &str, eof: bool) -> Segment {
+        let prefix = match self.parser.opening().chars().next() {
+            Some(prefix) => prefix,
+            None => return Segment::Text(pending.len()),
+        };
+
+        match pending.find(prefix) {
+            Some(0) => self.placeholder_segment(pending, prefix, eof),
+            Some(idx) => Segment::Text(idx),
+            None => Segment::Text(pending.len()),
+        }
+    }
+
+    fn placeholder_segment(&self, pending: &str, prefix: char, eof: bool) -> Segment {
+        let opening = self.parser.opening();
+        let excluded_opening = self.parser.excluded_opening();
+
+        let idx = if pending.starts_with(opening) {
+            opening.len()
+        } else if pending.starts_with(excluded_opening) {
+            excluded_opening.len()
+        } else if !eof && excluded_opening.starts_with(pending) {
+            return Segment::Incomplete;
+        } else {
+            return Segment::Text(prefix.len_utf8());
+        };
+
+        match self.parser.placeholder_end(pending, idx) {
+            Some(end) => Segment::Placeholder(end),
+            None if eof => Segment::Placeholder(pending.len()),
+            None if pending.len() > MAX_PENDING_LEN => Segment::Text(prefix.len_utf8()),
+            None => Segment::Incomplete,
+        }
+    }
+}
+
+fn decode_utf8(undecoded: &mut Vec<u8>, pending: &mut String, eof: bool) -> std::io::Result<()> {
+    let valid_up_to = match std::str::from_utf8(undecoded) {
+        Ok(decoded) => {
+            pending.push_str(decoded);
+            undecoded.clear();
+
+            return Ok(());
+        }
+        Err(err) if err.error_len().is_none() && !eof => err.valid_up_to(),
+        Err(err) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err)),
+    };
+
+    if let Ok(decoded) = std::str::from_utf8(&undecoded[..valid_up_to]) {
+        pending.push_str(decoded);
+    }
+    undecoded.drain(..valid_up_to);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use async_trait::async_trait;
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::JakartaCommand;
+
+    struct TestCommand {}
+
+    #[async_trait]
+    impl JakartaCommand for TestCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            args: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> String {
+            args.to_uppercase()
+        }
+    }
+
+    async fn interpolate_in_chunks(
+        jakarta: &Jakarta<'_>,
+        input: &str,
+        chunk_size: usize,
+    ) -> String {
+        let (mut input_writer, input_reader) = tokio::io::duplex(chunk_size);
+        let mut output = vec![];
+
+        let input = input.as_bytes().to_vec();
+        let writer = tokio::spawn(async move {
+            input_writer.write_all(&input).await.unwrap();
+        });
+
+        jakarta
+            .interpolate_reader(input_reader, &mut output)
+            .await
+            .unwrap();
+        writer.await.unwrap();
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_streams_interpolated_output() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("test", Arc::new(Mutex::new(TestCommand {})));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let input = "plain $ text ${test:abc} $${test:skipped} ${test:n_${test:ested}} end$";
+
+        for chunk_size in [1, 2, 3, 7, 64] {
+            let result = interpolate_in_chunks(&jakarta, input, chunk_size).await;
+
+            assert_eq!(
+                result,
+                "plain $ text ABC ${test:skipped} N_ESTED end$".to_owned()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn it_streams_multibyte_characters_across_chunks() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("test", Arc::new(Mutex::new(TestCommand {})));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let input = "äöü ${test:straße} 😀 ${test:unclosed";
+
+        for chunk_size in [1, 2, 3] {
+            let result = interpolate_in_chunks(&jakarta, input, chunk_size).await;
+
+            assert_eq!(result, "äöü STRASSE 😀 ${test:unclosed".to_owned());
+        }
+    }
+
+    #[tokio::test]
+    async fn it_flushes_unclosed_placeholders_past_the_limit() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+        commands.insert("test", Arc::new(Mutex::new(TestCommand {})));
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let (mut input_writer, input_reader) = tokio::io::duplex(CHUNK_SIZE);
+        let (output_writer, mut output_reader) = tokio::io::duplex(4 * MAX_PENDING_LEN);
+        let interpolation = tokio::spawn(async move {
+            jakarta
+                .interpolate_reader(input_reader, output_writer)
+                .await
+        });
+
+        let unclosed = format!("${{test:{}", "a".repeat(2 * MAX_PENDING_LEN));
+        input_writer.write_all(unclosed.as_bytes()).await.unwrap();
+
+        let mut flushed = vec![0; MAX_PENDING_LEN];
+        tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            output_reader.read_exact(&mut flushed),
+        )
+        .await
+        .expect("the unclosed placeholder was buffered past the limit")
+        .unwrap();
+
+        input_writer.write_all(b" ${test:x} tail").await.unwrap();
+        drop(input_writer);
+        interpolation.await.unwrap().unwrap();
+
+        let mut rest = vec![];
+        output_reader.read_to_end(&mut rest).await.unwrap();
+        flushed.extend(rest);
+
+        assert_eq!(
+            String::from_utf8(flushed).unwrap(),
+            format!("{unclosed} X tail")
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_invalid_utf8() {
+        let jakarta = Jakarta::new(HashMap::new()).unwrap();
+        let mut output = vec![];
+
+        let result = jakarta
+            .interpolate_reader(&[0x66, 0xff, 0x66][..], &mut output)
+            .await;
+
+        assert!(matches!(result, Err(JakartaError::Io(_))));
+    }
+
+    struct CountingCommand {
+        invocations: usize,
+        sessions: usize,
+    }
+
+    #[async_trait]
+    impl JakartaCommand for CountingCommand {
+        async fn process(
+            &mut self,
+            _: String,
+            _: Option<String>,
+            _: String,
+            _: Option<String>,
+            _: Option<String>,
+        ) -> String {
+            self.invocations += 1;
+            self.invocations.to_string()
+        }
+
+        fn cacheable(&self) -> bool {
+            true
+        }
+
+        fn end_interpolation(&mut self) {
+            self.sessions += 1;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_streams_in_a_single_interpolation() {
+        let mut commands: HashMap<&str, Arc<Mutex<dyn JakartaCommand>>> = HashMap::new();
+
+        let counting_cmd = Arc::new(Mutex::new(CountingCommand {
+            invocations: 0,
+            sessions: 0,
+        }));
+        commands.insert("count", counting_cmd.clone());
+        let jakarta = Jakarta::new(commands).unwrap();
+
+        let result = interpolate_in_chunks(&jakarta, "${count:a} ${count:a} ${count:b}", 3).await;
+
+        assert_eq!(result, "1 1 2".to_owned());
+        assert_eq!(counting_cmd.lock().await.invocations, 2);
+        assert_eq!(counting_cmd.lock().await.sessions, 1);
+    }
+}